@@ -0,0 +1,141 @@
+use std::fs::File;
+
+use bevy::prelude::*;
+use rand::prelude::*;
+use ron::de::from_reader;
+use serde::Deserialize;
+
+use crate::{
+    collision::MissileDetonated, enemy::WaveStarted, rng::GameRng, state::GameState, team::Team,
+    Velocity,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct BallisticsConfig {
+    // Missiles fly in straight lines when disabled
+    pub enabled: bool,
+    // Downwards acceleration of enemy missiles in pixels per second squared
+    pub gravity: f32,
+    // Strongest sideways acceleration the wind can have, a new wind between
+    // `-max_wind` and `max_wind` is picked at the start of each wave
+    pub max_wind: f32,
+}
+
+impl BallisticsConfig {
+    pub fn load(path: &str) -> Self {
+        let f = File::open(path).expect("Could not open file");
+
+        match from_reader(f) {
+            Ok(x) => x,
+            Err(e) => {
+                println!("Failed to load ballistics: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+// Resource with the forces acting on ballistic missiles
+#[derive(Debug)]
+pub struct Ballistics {
+    pub config: BallisticsConfig,
+    pub wind: f32,
+}
+
+impl Ballistics {
+    // Gravity only pulls on enemy missiles, the wind pushes both teams
+    pub fn acceleration(&self, team: Team) -> Vec2 {
+        match team {
+            Team::Player => Vec2::new(self.wind, 0.0),
+            Team::Enemy => Vec2::new(self.wind, -self.config.gravity),
+        }
+    }
+
+    // Velocity to launch a missile with so it lands on `target` under this
+    // team's acceleration. The flight time is what it would take to fly
+    // straight there at `speed` so missiles still arrive when expected.
+    //
+    // Returns the velocity along with the flight time.
+    pub fn launch(&self, team: Team, position: Vec2, target: Vec2, speed: f32) -> (Vec2, f32) {
+        let distance = target - position;
+        let time = distance.length() / speed;
+
+        if time <= 0.0 {
+            return (Vec2::ZERO, 0.0);
+        }
+
+        let velocity = distance / time - self.acceleration(team) * time / 2.0;
+        (velocity, time)
+    }
+}
+
+// Missile that is affected by gravity and wind
+pub struct Ballistic;
+
+// Ballistic missiles explode when their flight time runs out, they can move
+// too quickly near the end of an arc to reliably pass close to their target
+pub struct Fuse(pub Timer);
+
+pub struct BallisticsPlugin;
+impl Plugin for BallisticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        // Load the ballistics settings from ballistics.ron
+        let config = BallisticsConfig::load("./config/ballistics.ron");
+
+        app.insert_resource(Ballistics { config, wind: 0.0 })
+            .add_system_set(SystemSet::on_enter(GameState::Game).with_system(reset_wind.system()))
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(change_wind.system())
+                    .with_system(apply_ballistics.system().before("apply_velocity"))
+                    .with_system(check_fuses.system().before("collision_resolve")),
+            );
+    }
+}
+
+fn change_wind(
+    mut ballistics: ResMut<Ballistics>,
+    mut game_rng: ResMut<GameRng>,
+    mut wave_events: EventReader<WaveStarted>,
+) {
+    for _ in wave_events.iter() {
+        if !ballistics.config.enabled || ballistics.config.max_wind <= 0.0 {
+            continue;
+        }
+
+        let max_wind = ballistics.config.max_wind;
+        ballistics.wind = game_rng.stream("wind").gen_range(-max_wind..max_wind);
+        debug!("Wind: {:.1}", ballistics.wind);
+    }
+}
+
+fn reset_wind(mut ballistics: ResMut<Ballistics>) {
+    ballistics.wind = 0.0;
+}
+
+fn apply_ballistics(
+    time: Res<Time>,
+    ballistics: Res<Ballistics>,
+    mut query: Query<(&mut Velocity, &mut Transform, &Team), With<Ballistic>>,
+) {
+    for (mut velocity, mut transform, team) in query.iter_mut() {
+        velocity.0 += ballistics.acceleration(*team) * time.delta_seconds();
+        transform.rotation = Quat::from_rotation_z(Vec2::new(0.0, 1.0).angle_between(velocity.0));
+    }
+}
+
+fn check_fuses(
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Fuse, &Transform, &Team)>,
+    mut events: EventWriter<MissileDetonated>,
+) {
+    for (entity, mut fuse, transform, team) in query.iter_mut() {
+        if fuse.0.tick(time.delta()).just_finished() {
+            events.send(MissileDetonated {
+                missile: entity,
+                team: *team,
+                position: transform.translation,
+            });
+        }
+    }
+}
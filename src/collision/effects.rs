@@ -0,0 +1,282 @@
+use bevy::prelude::*;
+
+use crate::{
+    audio::PlayAudio,
+    consts::{
+        CHAIN_EXPLOSION_SIZE, EXPLOSION_SIZE_SCALE, MISSILE_HIT_VALUE, MISSILE_SPEED_BONUS,
+        PLAYER_MISSILE_VELOCITY, SCORE_POWERUP_VALUE,
+    },
+    debris::{DebrisType, SpawnDebris},
+    explosion::SpawnExplosion,
+    game_status::{ChainLink, Combo, RecordStat, Stat, UpdateScore},
+    player_status::{PlayerStatus, SetPlayerExplosionSize, SetPlayerHoming, SetPlayerMissileSpeed},
+    powerups::PowerupType,
+    silo::{SiloMissileCountUpdate, Wrecked},
+    team::{CollisionLayers, Team},
+    AssetHandles, Silo,
+};
+
+use super::resolve::Collisions;
+
+// Every entity destroyed in a collision is despawned here, silos are wrecked
+// instead
+pub fn despawn_collided(mut commands: Commands, collisions: Res<Collisions>, silos: Query<&Silo>) {
+    let collided = collisions
+        .explosion_missile
+        .iter()
+        .map(|e| e.missile)
+        .chain(collisions.explosion_flier.iter().map(|e| e.flier))
+        .chain(collisions.explosion_powerup.iter().map(|e| e.powerup))
+        .chain(collisions.explosion_structure.iter().map(|e| e.structure))
+        .chain(
+            collisions
+                .missile_missile
+                .iter()
+                .flat_map(|e| vec![e.missile, e.target]),
+        )
+        .chain(
+            collisions
+                .missile_powerup
+                .iter()
+                .flat_map(|e| vec![e.missile, e.powerup]),
+        )
+        .chain(
+            collisions
+                .missile_structure
+                .iter()
+                .flat_map(|e| vec![e.missile, e.structure]),
+        )
+        .chain(collisions.missile_ground.iter().map(|e| e.missile))
+        .chain(collisions.missile_detonated.iter().map(|e| e.missile));
+
+    for entity in collided.filter(|entity| silos.get(*entity).is_err()) {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Only the player scores. Each missile destroyed by an explosion is a link in
+// a chain, later links are worth more.
+pub fn score_collisions(
+    collisions: Res<Collisions>,
+    mut score_events: EventWriter<UpdateScore>,
+    mut chain_events: EventWriter<ChainLink>,
+) {
+    for e in collisions
+        .explosion_missile
+        .iter()
+        .filter(|e| e.team == Team::Player)
+    {
+        score_events.send(UpdateScore(
+            e.missile_type.value() * Combo::multiplier(e.link),
+        ));
+        chain_events.send(ChainLink(e.link));
+    }
+
+    for e in collisions.explosion_flier.iter() {
+        score_events.send(UpdateScore(e.flier_type.value()));
+    }
+
+    for e in collisions
+        .missile_missile
+        .iter()
+        .filter(|e| e.team == Team::Player)
+    {
+        score_events.send(UpdateScore(MISSILE_HIT_VALUE.max(e.target_type.value())));
+    }
+}
+
+pub fn record_collision_stats(
+    collisions: Res<Collisions>,
+    mut stat_events: EventWriter<RecordStat>,
+) {
+    let intercepted = collisions
+        .explosion_missile
+        .iter()
+        .map(|e| e.team)
+        .chain(collisions.missile_missile.iter().map(|e| e.team))
+        .filter(|team| *team == Team::Player)
+        .count();
+    for _ in 0..intercepted {
+        stat_events.send(RecordStat(Stat::Intercepted));
+    }
+
+    for e in collisions.explosion_structure.iter() {
+        stat_events.send(RecordStat(structure_lost(e.debris_type)));
+    }
+
+    for e in collisions.missile_structure.iter() {
+        if e.team == Team::Enemy {
+            stat_events.send(RecordStat(Stat::Landed));
+        }
+        stat_events.send(RecordStat(structure_lost(e.debris_type)));
+    }
+
+    for e in collisions.missile_ground.iter() {
+        if e.team == Team::Enemy {
+            stat_events.send(RecordStat(Stat::Landed));
+        }
+    }
+}
+
+fn structure_lost(debris_type: DebrisType) -> Stat {
+    match debris_type {
+        DebrisType::Building => Stat::BuildingLost,
+        DebrisType::Silo => Stat::SiloLost,
+    }
+}
+
+// Missiles explode when they hit something or reach their target, missiles
+// destroyed by an explosion set off a smaller one of their own that carries on
+// the chain
+pub fn collision_explosions(
+    collisions: Res<Collisions>,
+    player_status: Res<PlayerStatus>,
+    mut explosion_events: EventWriter<SpawnExplosion>,
+) {
+    for e in collisions.explosion_missile.iter() {
+        explosion_events.send(SpawnExplosion {
+            position: e.position,
+            team: e.team,
+            size: CHAIN_EXPLOSION_SIZE,
+            chain: e.link,
+        });
+    }
+
+    let detonations = collisions
+        .missile_missile
+        .iter()
+        .map(|e| (e.position, e.team))
+        .chain(
+            collisions
+                .missile_powerup
+                .iter()
+                .map(|e| (e.position, e.team)),
+        )
+        .chain(
+            collisions
+                .missile_structure
+                .iter()
+                .map(|e| (e.position, e.team)),
+        )
+        .chain(
+            collisions
+                .missile_ground
+                .iter()
+                .map(|e| (e.position, e.team)),
+        )
+        .chain(
+            collisions
+                .missile_detonated
+                .iter()
+                .map(|e| (e.position, e.team)),
+        );
+
+    for (position, team) in detonations {
+        explosion_events.send(SpawnExplosion {
+            position,
+            team,
+            size: match team {
+                Team::Player => player_status.explosion_size,
+                Team::Enemy => 1.0,
+            },
+            chain: 0,
+        });
+    }
+}
+
+pub fn collision_debris(collisions: Res<Collisions>, mut debris_events: EventWriter<SpawnDebris>) {
+    let destroyed = collisions
+        .explosion_structure
+        .iter()
+        .map(|e| (e.x_position, e.debris_type))
+        .chain(
+            collisions
+                .missile_structure
+                .iter()
+                .map(|e| (e.x_position, e.debris_type)),
+        );
+
+    for (x_position, debris_type) in destroyed {
+        debris_events.send(SpawnDebris {
+            x_position,
+            debris_type,
+        });
+    }
+}
+
+pub fn collision_audio(
+    collisions: Res<Collisions>,
+    asset_handles: Res<AssetHandles>,
+    mut audio_events: EventWriter<PlayAudio>,
+) {
+    for _ in collisions.explosion_flier.iter() {
+        audio_events.send(PlayAudio {
+            handle: asset_handles.explosion_audio.clone(),
+        });
+    }
+
+    let powerups =
+        collisions.explosion_powerup.iter().count() + collisions.missile_powerup.iter().count();
+    for _ in 0..powerups {
+        audio_events.send(PlayAudio {
+            handle: asset_handles.powerup_audio.clone(),
+        });
+    }
+}
+
+// Destroyed silos lose their missiles and are left in place, hidden behind
+// their debris, so they can be rebuilt later
+pub fn wreck_silos(
+    mut commands: Commands,
+    collisions: Res<Collisions>,
+    mut silos: Query<(&mut Silo, &mut CollisionLayers, &mut Visible), Without<Wrecked>>,
+    mut silo_ui_events: EventWriter<SiloMissileCountUpdate>,
+) {
+    let destroyed = collisions
+        .explosion_structure
+        .iter()
+        .map(|e| e.structure)
+        .chain(collisions.missile_structure.iter().map(|e| e.structure));
+
+    for entity in destroyed {
+        if let Ok((mut silo, mut layers, mut visible)) = silos.get_mut(entity) {
+            silo.missiles = 0;
+            *layers = CollisionLayers::new(CollisionLayers::NONE, CollisionLayers::NONE);
+            visible.is_visible = false;
+            commands.entity(entity).insert(Wrecked);
+
+            silo_ui_events.send(SiloMissileCountUpdate {
+                silo: silo.index,
+                count: 0,
+            });
+        }
+    }
+}
+
+// Powerups work the same whether they were shot or caught in an explosion
+pub fn apply_powerups(
+    collisions: Res<Collisions>,
+    mut score_events: EventWriter<UpdateScore>,
+    mut explosion_size_events: EventWriter<SetPlayerExplosionSize>,
+    mut missile_speed_events: EventWriter<SetPlayerMissileSpeed>,
+    mut homing_events: EventWriter<SetPlayerHoming>,
+) {
+    let collected = collisions
+        .explosion_powerup
+        .iter()
+        .map(|e| e.powerup_type)
+        .chain(collisions.missile_powerup.iter().map(|e| e.powerup_type));
+
+    for powerup_type in collected {
+        match powerup_type {
+            PowerupType::Score => score_events.send(UpdateScore(SCORE_POWERUP_VALUE)),
+            PowerupType::ExplosionSize => {
+                explosion_size_events.send(SetPlayerExplosionSize(EXPLOSION_SIZE_SCALE))
+            }
+            PowerupType::MissileSpeed => missile_speed_events.send(SetPlayerMissileSpeed(
+                PLAYER_MISSILE_VELOCITY * MISSILE_SPEED_BONUS,
+            )),
+            PowerupType::Homing => homing_events.send(SetPlayerHoming(true)),
+        };
+    }
+}
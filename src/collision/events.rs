@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+use crate::{
+    debris::DebrisType, enemy::FlierType, missile::MissileType, powerups::PowerupType, team::Team,
+};
+
+// Events sent by the collision systems. They only detect what hit what, the
+// effects of each collision are applied by the systems in `effects.rs` once
+// `resolve.rs` has made sure nothing is destroyed twice.
+
+// Explosion hit a missile, `team` is the explosion's. `link` is the missile's
+// place in the chain of explosions that destroyed it.
+#[derive(Clone)]
+pub struct ExplosionHitMissile {
+    pub missile: Entity,
+    pub missile_type: MissileType,
+    pub team: Team,
+    pub position: Vec3,
+    pub link: u32,
+}
+
+// Explosion hit a Flier
+#[derive(Clone)]
+pub struct ExplosionHitFlier {
+    pub flier: Entity,
+    pub flier_type: FlierType,
+}
+
+// Explosion hit a Powerup
+#[derive(Clone)]
+pub struct ExplosionHitPowerup {
+    pub powerup: Entity,
+    pub powerup_type: PowerupType,
+}
+
+// Explosion hit a building or silo
+#[derive(Clone)]
+pub struct ExplosionHitStructure {
+    pub structure: Entity,
+    pub debris_type: DebrisType,
+    pub x_position: f32,
+}
+
+// Missile flew into another missile. `team` and `position` are the first
+// missile's, the one whose mask covered the target.
+#[derive(Clone)]
+pub struct MissileHitMissile {
+    pub missile: Entity,
+    pub target: Entity,
+    pub target_type: MissileType,
+    pub team: Team,
+    pub position: Vec3,
+}
+
+// Missile flew into a Powerup, `team` and `position` are the missile's
+#[derive(Clone)]
+pub struct MissileHitPowerup {
+    pub missile: Entity,
+    pub powerup: Entity,
+    pub powerup_type: PowerupType,
+    pub team: Team,
+    pub position: Vec3,
+}
+
+// Missile landed on a building or silo, `team` and `position` are the
+// missile's
+#[derive(Clone)]
+pub struct MissileHitStructure {
+    pub missile: Entity,
+    pub structure: Entity,
+    pub debris_type: DebrisType,
+    pub team: Team,
+    pub position: Vec3,
+    pub x_position: f32,
+}
+
+// Missile from either team reached the ground
+#[derive(Clone)]
+pub struct MissileHitGround {
+    pub missile: Entity,
+    pub team: Team,
+    pub position: Vec3,
+}
+
+// Missile reached its target or its fuse ran out
+#[derive(Clone)]
+pub struct MissileDetonated {
+    pub missile: Entity,
+    pub team: Team,
+    pub position: Vec3,
+}
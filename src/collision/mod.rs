@@ -0,0 +1,593 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{
+    debris::DebrisType,
+    enemy::{Damage, Flier, Health},
+    explosion::{Chain, Explosion},
+    ground::GroundLevel,
+    missile::{Missile, MissileType},
+    powerups::PowerupType,
+    state::GameState,
+    team::{CollisionLayers, Team},
+    Building, Silo,
+};
+
+mod effects;
+mod events;
+mod resolve;
+mod spatial_grid;
+
+pub use self::events::{
+    ExplosionHitFlier, ExplosionHitMissile, ExplosionHitPowerup, ExplosionHitStructure,
+    MissileDetonated, MissileHitGround, MissileHitMissile, MissileHitPowerup, MissileHitStructure,
+};
+pub use self::spatial_grid::{SpatialGrid, COLLISION_CHECKS};
+
+use self::{
+    effects::{
+        apply_powerups, collision_audio, collision_debris, collision_explosions, despawn_collided,
+        record_collision_stats, score_collisions, wreck_silos,
+    },
+    resolve::{resolve_collisions, Collisions},
+    spatial_grid::{rebuild_spatial_grid, setup_collision_diagnostics, SpatialGridConfig},
+};
+
+pub struct CircleCollider(pub f32);
+
+// Axis aligned box collider, `offset` is from the entity's position to the
+// centre of the box
+#[derive(Debug, Clone, Copy)]
+pub struct AabbCollider {
+    pub half_extents: Vec2,
+    pub offset: Vec2,
+}
+
+impl AabbCollider {
+    // Fit the box around the opaque pixels of a texture so transparent
+    // padding in the sprite doesn't count
+    pub fn from_texture(texture: &Texture) -> Self {
+        let width = texture.size.width as usize;
+        let height = texture.size.height as usize;
+        let full = Self {
+            half_extents: Vec2::new(width as f32, height as f32) / 2.0,
+            offset: Vec2::ZERO,
+        };
+
+        // Only 8 bit RGBA textures are checked for transparency
+        let pixel_size = texture.format.pixel_size();
+        if pixel_size != 4 || texture.data.len() < width * height * pixel_size {
+            return full;
+        }
+
+        let mut min = (usize::MAX, usize::MAX);
+        let mut max = (0, 0);
+        for y in 0..height {
+            for x in 0..width {
+                let alpha = texture.data[(y * width + x) * pixel_size + 3];
+                if alpha > 0 {
+                    min = (min.0.min(x), min.1.min(y));
+                    max = (max.0.max(x), max.1.max(y));
+                }
+            }
+        }
+
+        if min.0 > max.0 {
+            return full;
+        }
+
+        let half_extents = Vec2::new((max.0 + 1 - min.0) as f32, (max.1 + 1 - min.1) as f32) / 2.0;
+        // Texture rows go down the screen so flip y
+        let offset = Vec2::new(
+            (min.0 + max.0 + 1) as f32 / 2.0 - width as f32 / 2.0,
+            height as f32 / 2.0 - (min.1 + max.1 + 1) as f32 / 2.0,
+        );
+
+        Self {
+            half_extents,
+            offset,
+        }
+    }
+}
+
+// Position at the start of the frame. Fast moving entities are tested along
+// the path they moved this frame so they can't skip past each other.
+pub struct PreviousPosition(pub Vec3);
+
+pub struct CollisionPlugin;
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        // Load the broadphase settings from spatial_grid.ron
+        let grid = SpatialGrid::new(SpatialGridConfig::load("./config/spatial_grid.ron"));
+
+        app.add_event::<ExplosionHitMissile>()
+            .add_event::<ExplosionHitFlier>()
+            .add_event::<ExplosionHitPowerup>()
+            .add_event::<ExplosionHitStructure>()
+            .add_event::<MissileHitMissile>()
+            .add_event::<MissileHitPowerup>()
+            .add_event::<MissileHitStructure>()
+            .add_event::<MissileHitGround>()
+            .add_event::<MissileDetonated>()
+            .init_resource::<Collisions>()
+            .insert_resource(grid)
+            .add_startup_system(setup_collision_diagnostics.system())
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(store_previous_positions.system().before("apply_velocity"))
+                    .with_system(
+                        rebuild_spatial_grid
+                            .system()
+                            .label("spatial_grid")
+                            .after("apply_velocity"),
+                    ),
+            )
+            .add_system_set(
+                // Run after everything has moved and been sorted into the grid
+                // so the swept tests cover this frame's movement
+                SystemSet::on_update(GameState::Game)
+                    .label("collision_detection")
+                    .after("spatial_grid")
+                    .with_system(explosion_collisions.system())
+                    .with_system(missile_collisions.system())
+                    .with_system(missile_structure_collisions.system())
+                    .with_system(explosion_structure_collisions.system())
+                    .with_system(missile_ground_collisions.system())
+                    .with_system(powerup_collisions.system()),
+            )
+            .add_system_set(
+                // Missiles that reach their target are resolved along with
+                // everything else, they send `MissileDetonated` before this
+                SystemSet::on_update(GameState::Game)
+                    .label("collision_resolve")
+                    .after("collision_detection")
+                    .with_system(resolve_collisions.system()),
+            )
+            .add_system_set(
+                // The detection systems only send collision events, these
+                // apply what happens as a result
+                SystemSet::on_update(GameState::Game)
+                    .after("collision_resolve")
+                    .with_system(despawn_collided.system())
+                    .with_system(score_collisions.system())
+                    .with_system(record_collision_stats.system())
+                    .with_system(collision_explosions.system())
+                    .with_system(collision_debris.system())
+                    .with_system(collision_audio.system())
+                    .with_system(apply_powerups.system())
+                    .with_system(wreck_silos.system()),
+            );
+    }
+}
+
+fn store_previous_positions(mut query: Query<(&Transform, &mut PreviousPosition)>) {
+    for (transform, mut previous) in query.iter_mut() {
+        previous.0 = transform.translation;
+    }
+}
+
+// Where an entity was at the start of the frame, entities that don't store it
+// are treated as not having moved
+pub fn start_position(transform: &Transform, previous: Option<&PreviousPosition>) -> Vec2 {
+    previous
+        .map(|p| p.0.truncate())
+        .unwrap_or_else(|| transform.translation.truncate())
+}
+
+// Check if the segment from `start` to `end` passes within `radius` of `center`
+pub fn segment_hits_circle(start: Vec2, end: Vec2, center: Vec2, radius: f32) -> bool {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+
+    let t = if length_squared > 0.0 {
+        ((center - start).dot(segment) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    (start + segment * t).distance_squared(center) < radius.powi(2)
+}
+
+// Check if two moving circles touched at any point this frame. Measured from
+// `b` the movement of `a` is a single segment, so it is tested against a
+// circle at the origin with both radii.
+pub fn swept_circles_hit(
+    a_start: Vec2,
+    a_end: Vec2,
+    b_start: Vec2,
+    b_end: Vec2,
+    radius: f32,
+) -> bool {
+    segment_hits_circle(a_start - b_start, a_end - b_end, Vec2::ZERO, radius)
+}
+
+// Explosions hit missiles, Fliers and Powerups their mask covers. Enemies with
+// Health are damaged instead of destroyed.
+//
+// Destroyed missiles set off their own explosion which can destroy more
+// missiles, each link in the chain multiplies the score.
+fn explosion_collisions(
+    grid: Res<SpatialGrid>,
+    explosions: Query<(
+        Entity,
+        &Explosion,
+        &Team,
+        Option<&Chain>,
+        &CollisionLayers,
+        &CircleCollider,
+        &Transform,
+    )>,
+    missiles: Query<(
+        Entity,
+        &Missile,
+        &MissileType,
+        &CollisionLayers,
+        &Transform,
+        Option<&PreviousPosition>,
+    )>,
+    fliers: Query<(
+        Entity,
+        &Flier,
+        &CollisionLayers,
+        &Transform,
+        &CircleCollider,
+    )>,
+    powerups: Query<(
+        Entity,
+        &PowerupType,
+        &CollisionLayers,
+        &Transform,
+        &CircleCollider,
+    )>,
+    damageable: Query<(Entity, &CollisionLayers, &Transform, &CircleCollider), With<Health>>,
+    mut damage_events: EventWriter<Damage>,
+    mut missile_events: EventWriter<ExplosionHitMissile>,
+    mut flier_events: EventWriter<ExplosionHitFlier>,
+    mut powerup_events: EventWriter<ExplosionHitPowerup>,
+) {
+    // Entities can be inside more than one explosion
+    let mut destroyed = HashSet::default();
+
+    for (x_entity, _, x_team, chain, x_layers, x_collider, x_transform) in explosions.iter() {
+        let nearby = grid.query_circle(x_transform.translation.truncate(), x_collider.0);
+
+        for &candidate in nearby.iter() {
+            if destroyed.contains(&candidate) {
+                continue;
+            }
+
+            if let Ok((m_entity, _, m_type, m_layers, m_transform, m_previous)) =
+                missiles.get(candidate)
+            {
+                let hit = x_layers.hits(m_layers)
+                    && segment_hits_circle(
+                        start_position(m_transform, m_previous),
+                        m_transform.translation.truncate(),
+                        x_transform.translation.truncate(),
+                        x_collider.0,
+                    );
+
+                if hit {
+                    destroyed.insert(m_entity);
+                    missile_events.send(ExplosionHitMissile {
+                        missile: m_entity,
+                        missile_type: *m_type,
+                        team: *x_team,
+                        position: m_transform.translation,
+                        link: chain.map(|c| c.0).unwrap_or(0) + 1,
+                    });
+                }
+            }
+
+            if let Ok((f_entity, flier, f_layers, f_transform, f_collider)) = fliers.get(candidate)
+            {
+                let d = x_transform
+                    .translation
+                    .distance_squared(f_transform.translation);
+
+                if x_layers.hits(f_layers) && d < (x_collider.0 + f_collider.0).powi(2) {
+                    destroyed.insert(f_entity);
+                    flier_events.send(ExplosionHitFlier {
+                        flier: f_entity,
+                        flier_type: flier.flier_type,
+                    });
+                }
+            }
+
+            if let Ok((h_entity, h_layers, h_transform, h_collider)) = damageable.get(candidate) {
+                let d = x_transform
+                    .translation
+                    .distance_squared(h_transform.translation);
+
+                if x_layers.hits(h_layers) && d < (x_collider.0 + h_collider.0).powi(2) {
+                    damage_events.send(Damage {
+                        target: h_entity,
+                        source: x_entity,
+                    });
+                }
+            }
+
+            if let Ok((pow_entity, pow_type, pow_layers, pow_transform, pow_collider)) =
+                powerups.get(candidate)
+            {
+                let d = x_transform
+                    .translation
+                    .distance_squared(pow_transform.translation);
+
+                if x_layers.hits(pow_layers) && d < (x_collider.0 + pow_collider.0).powi(2) {
+                    destroyed.insert(pow_entity);
+                    powerup_events.send(ExplosionHitPowerup {
+                        powerup: pow_entity,
+                        powerup_type: *pow_type,
+                    });
+                }
+            }
+        }
+    }
+}
+
+// Missiles fly into other missiles their mask covers
+fn missile_collisions(
+    grid: Res<SpatialGrid>,
+    missiles: Query<(
+        Entity,
+        &Missile,
+        &MissileType,
+        &Team,
+        &CollisionLayers,
+        &Transform,
+        &CircleCollider,
+        Option<&PreviousPosition>,
+    )>,
+    mut events: EventWriter<MissileHitMissile>,
+) {
+    // Each missile can only be destroyed once
+    let mut destroyed = HashSet::default();
+
+    for (a_entity, _, _, a_team, a_layers, a_transform, a_collider, a_previous) in missiles.iter() {
+        let a_start = start_position(a_transform, a_previous);
+        let a_end = a_transform.translation.truncate();
+
+        for &candidate in grid.query_swept(a_start, a_end, a_collider.0).iter() {
+            if destroyed.contains(&a_entity) {
+                break;
+            }
+
+            if candidate == a_entity || destroyed.contains(&candidate) {
+                continue;
+            }
+
+            if let Ok((b_entity, _, b_type, _, b_layers, b_transform, b_collider, b_previous)) =
+                missiles.get(candidate)
+            {
+                let hit = a_layers.hits(b_layers)
+                    && swept_circles_hit(
+                        a_start,
+                        a_end,
+                        start_position(b_transform, b_previous),
+                        b_transform.translation.truncate(),
+                        a_collider.0 + b_collider.0,
+                    );
+
+                if hit {
+                    destroyed.insert(a_entity);
+                    destroyed.insert(b_entity);
+                    events.send(MissileHitMissile {
+                        missile: a_entity,
+                        target: b_entity,
+                        target_type: *b_type,
+                        team: *a_team,
+                        position: a_transform.translation,
+                    });
+                }
+            }
+        }
+    }
+}
+
+// Missiles land on the buildings/silos their mask covers
+fn missile_structure_collisions(
+    grid: Res<SpatialGrid>,
+    missiles: Query<(
+        Entity,
+        &Missile,
+        &Team,
+        &CollisionLayers,
+        &Transform,
+        &CircleCollider,
+    )>,
+    structures: Query<
+        (
+            Entity,
+            &CollisionLayers,
+            &Transform,
+            &AabbCollider,
+            Option<&Building>,
+        ),
+        Or<(With<Building>, With<Silo>)>,
+    >,
+    mut events: EventWriter<MissileHitStructure>,
+) {
+    // Structures can be hit by more than one missile
+    let mut destroyed = HashSet::default();
+
+    for (missile, _, m_team, m_layers, m_transform, m_collider) in missiles.iter() {
+        let m_position = m_transform.translation.truncate();
+
+        for &candidate in grid.query_circle(m_position, m_collider.0).iter() {
+            if destroyed.contains(&candidate) {
+                continue;
+            }
+
+            if let Ok((structure_entity, s_layers, s_transform, aabb, b)) =
+                structures.get(candidate)
+            {
+                let hit = m_layers.hits(s_layers)
+                    && circle_hits_aabb(
+                        m_position,
+                        m_collider.0,
+                        s_transform.translation.truncate(),
+                        aabb,
+                    );
+
+                if hit {
+                    destroyed.insert(structure_entity);
+                    events.send(MissileHitStructure {
+                        missile,
+                        structure: structure_entity,
+                        debris_type: match b {
+                            Some(_) => DebrisType::Building,
+                            None => DebrisType::Silo,
+                        },
+                        team: *m_team,
+                        position: m_transform.translation,
+                        x_position: s_transform.translation.x,
+                    });
+                    break;
+                }
+            }
+        }
+    }
+}
+
+// Explosions destroy every building/silo they touch that their mask covers,
+// so missiles that land close to a structure still take it out
+fn explosion_structure_collisions(
+    grid: Res<SpatialGrid>,
+    explosions: Query<(&Explosion, &CollisionLayers, &CircleCollider, &Transform)>,
+    structures: Query<
+        (
+            Entity,
+            &CollisionLayers,
+            &Transform,
+            &AabbCollider,
+            Option<&Building>,
+        ),
+        Or<(With<Building>, With<Silo>)>,
+    >,
+    mut events: EventWriter<ExplosionHitStructure>,
+) {
+    // Structures can be inside more than one explosion
+    let mut destroyed = HashSet::default();
+
+    for (_, x_layers, x_collider, x_transform) in explosions.iter() {
+        let x_position = x_transform.translation.truncate();
+
+        for &candidate in grid.query_circle(x_position, x_collider.0).iter() {
+            if destroyed.contains(&candidate) {
+                continue;
+            }
+
+            if let Ok((structure_entity, s_layers, s_transform, aabb, b)) =
+                structures.get(candidate)
+            {
+                let hit = x_layers.hits(s_layers)
+                    && circle_hits_aabb(
+                        x_position,
+                        x_collider.0,
+                        s_transform.translation.truncate(),
+                        aabb,
+                    );
+
+                if hit {
+                    destroyed.insert(structure_entity);
+                    events.send(ExplosionHitStructure {
+                        structure: structure_entity,
+                        debris_type: match b {
+                            Some(_) => DebrisType::Building,
+                            None => DebrisType::Silo,
+                        },
+                        x_position: s_transform.translation.x,
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn missile_ground_collisions(
+    missiles: Query<(Entity, &Missile, &Transform, &Team)>,
+    ground_level: Res<GroundLevel>,
+    mut events: EventWriter<MissileHitGround>,
+) {
+    for (entity, _, transform, team) in missiles.iter() {
+        if transform.translation.y < ground_level.height_at(transform.translation.x) {
+            events.send(MissileHitGround {
+                missile: entity,
+                team: *team,
+                position: transform.translation,
+            });
+        }
+    }
+}
+
+// Missiles fly into Powerups their mask covers
+fn powerup_collisions(
+    grid: Res<SpatialGrid>,
+    missiles: Query<(
+        Entity,
+        &Missile,
+        &Team,
+        &CollisionLayers,
+        &Transform,
+        Option<&PreviousPosition>,
+    )>,
+    powerups: Query<(
+        Entity,
+        &PowerupType,
+        &CollisionLayers,
+        &Transform,
+        &CircleCollider,
+        Option<&PreviousPosition>,
+    )>,
+    mut events: EventWriter<MissileHitPowerup>,
+) {
+    // Only the first missile to reach a powerup collects it
+    let mut collected = HashSet::default();
+
+    for (m_entity, _, m_team, m_layers, m_transform, m_previous) in missiles.iter() {
+        let m_start = start_position(m_transform, m_previous);
+        let m_end = m_transform.translation.truncate();
+
+        for &candidate in grid.query_swept(m_start, m_end, 0.0).iter() {
+            if collected.contains(&candidate) {
+                continue;
+            }
+
+            if let Ok((p_entity, p_type, p_layers, p_transform, p_collider, p_previous)) =
+                powerups.get(candidate)
+            {
+                let hit = m_layers.hits(p_layers)
+                    && swept_circles_hit(
+                        m_start,
+                        m_end,
+                        start_position(p_transform, p_previous),
+                        p_transform.translation.truncate(),
+                        p_collider.0,
+                    );
+
+                if hit {
+                    collected.insert(p_entity);
+                    events.send(MissileHitPowerup {
+                        missile: m_entity,
+                        powerup: p_entity,
+                        powerup_type: *p_type,
+                        team: *m_team,
+                        position: m_transform.translation,
+                    });
+                    break;
+                }
+            }
+        }
+    }
+}
+
+// Check if a circle overlaps an entity's AabbCollider by finding the closest
+// point in the box to the circle's centre
+pub fn circle_hits_aabb(center: Vec2, radius: f32, position: Vec2, aabb: &AabbCollider) -> bool {
+    let box_center = position + aabb.offset;
+    let closest = center
+        .max(box_center - aabb.half_extents)
+        .min(box_center + aabb.half_extents);
+
+    center.distance_squared(closest) < radius.powi(2)
+}
@@ -0,0 +1,97 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use super::events::{
+    ExplosionHitFlier, ExplosionHitMissile, ExplosionHitPowerup, ExplosionHitStructure,
+    MissileDetonated, MissileHitGround, MissileHitMissile, MissileHitPowerup, MissileHitStructure,
+};
+
+// Resource with every collision from this frame that the effect systems should
+// act on. The detection systems run in parallel and each only knows about its
+// own collisions, so a missile can be caught in an explosion and land on a
+// building in the same frame. Only the first collision each entity is part of
+// is kept here.
+#[derive(Default)]
+pub struct Collisions {
+    pub missile_missile: Vec<MissileHitMissile>,
+    pub explosion_missile: Vec<ExplosionHitMissile>,
+    pub missile_powerup: Vec<MissileHitPowerup>,
+    pub missile_structure: Vec<MissileHitStructure>,
+    pub missile_ground: Vec<MissileHitGround>,
+    pub missile_detonated: Vec<MissileDetonated>,
+    pub explosion_structure: Vec<ExplosionHitStructure>,
+    pub explosion_flier: Vec<ExplosionHitFlier>,
+    pub explosion_powerup: Vec<ExplosionHitPowerup>,
+}
+
+// The events are checked in the order of the fields above, so being shot down
+// wins over landing and the player gets the credit. Explosions aren't used up
+// by hitting something so only their targets are claimed.
+pub fn resolve_collisions(
+    mut collisions: ResMut<Collisions>,
+    mut missile_missile: EventReader<MissileHitMissile>,
+    mut explosion_missile: EventReader<ExplosionHitMissile>,
+    mut missile_powerup: EventReader<MissileHitPowerup>,
+    mut missile_structure: EventReader<MissileHitStructure>,
+    mut missile_ground: EventReader<MissileHitGround>,
+    mut missile_detonated: EventReader<MissileDetonated>,
+    mut explosion_structure: EventReader<ExplosionHitStructure>,
+    mut explosion_flier: EventReader<ExplosionHitFlier>,
+    mut explosion_powerup: EventReader<ExplosionHitPowerup>,
+) {
+    let mut claimed = HashSet::default();
+    // Claim every entity in a collision, or none of them if any were already
+    // destroyed by an earlier one
+    let mut claim = |entities: &[Entity]| {
+        if entities.iter().any(|entity| claimed.contains(entity)) {
+            return false;
+        }
+        claimed.extend(entities.iter().copied());
+        true
+    };
+
+    collisions.missile_missile = missile_missile
+        .iter()
+        .filter(|e| claim(&[e.missile, e.target]))
+        .cloned()
+        .collect();
+    collisions.explosion_missile = explosion_missile
+        .iter()
+        .filter(|e| claim(&[e.missile]))
+        .cloned()
+        .collect();
+    collisions.missile_powerup = missile_powerup
+        .iter()
+        .filter(|e| claim(&[e.missile, e.powerup]))
+        .cloned()
+        .collect();
+    collisions.missile_structure = missile_structure
+        .iter()
+        .filter(|e| claim(&[e.missile, e.structure]))
+        .cloned()
+        .collect();
+    collisions.missile_ground = missile_ground
+        .iter()
+        .filter(|e| claim(&[e.missile]))
+        .cloned()
+        .collect();
+    collisions.missile_detonated = missile_detonated
+        .iter()
+        .filter(|e| claim(&[e.missile]))
+        .cloned()
+        .collect();
+    collisions.explosion_structure = explosion_structure
+        .iter()
+        .filter(|e| claim(&[e.structure]))
+        .cloned()
+        .collect();
+    collisions.explosion_flier = explosion_flier
+        .iter()
+        .filter(|e| claim(&[e.flier]))
+        .cloned()
+        .collect();
+    collisions.explosion_powerup = explosion_powerup
+        .iter()
+        .filter(|e| claim(&[e.powerup]))
+        .cloned()
+        .collect();
+}
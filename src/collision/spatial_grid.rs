@@ -0,0 +1,191 @@
+use std::{
+    fs::File,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics},
+    prelude::*,
+    utils::HashMap,
+};
+use ron::de::from_reader;
+use serde::Deserialize;
+
+use crate::explosion::Explosion;
+
+use super::{start_position, AabbCollider, CircleCollider, PreviousPosition};
+
+// Number of entities handed to the collision tests each frame
+pub const COLLISION_CHECKS: DiagnosticId =
+    DiagnosticId::from_u128(0x5f4f_2a3c_9e61_4d1b_8a07_c2d4_1e6b_93f5);
+
+#[derive(Debug, Deserialize)]
+pub struct SpatialGridConfig {
+    // Every collider is tested against every other when disabled, only
+    // really useful for comparing against
+    pub enabled: bool,
+    // Width and height of each cell in pixels, should be around the size of
+    // the largest explosion
+    pub cell_size: f32,
+}
+
+impl SpatialGridConfig {
+    pub fn load(path: &str) -> Self {
+        let f = File::open(path).expect("Could not open file");
+
+        let config: SpatialGridConfig = match from_reader(f) {
+            Ok(x) => x,
+            Err(e) => {
+                println!("Failed to load spatial grid: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        // Cells with no size would never let an entity be sorted into them
+        if !config.cell_size.is_finite() || config.cell_size <= 0.0 {
+            println!(
+                "Failed to load spatial grid: cell_size must be above 0, not {}",
+                config.cell_size
+            );
+            std::process::exit(1);
+        }
+
+        config
+    }
+}
+
+// Resource sorting every collider into a uniform grid of cells so the
+// collision systems only test entities that are near each other. It is
+// rebuilt from scratch each frame once everything has moved.
+//
+// Moving entities are inserted with the bounds of the path they took this
+// frame so the swept tests still find them.
+pub struct SpatialGrid {
+    pub config: SpatialGridConfig,
+    cells: HashMap<(i32, i32), Vec<Entity>>,
+    entities: Vec<Entity>,
+    // Collision systems run in parallel and only read the grid
+    checks: AtomicUsize,
+}
+
+impl SpatialGrid {
+    pub fn new(config: SpatialGridConfig) -> Self {
+        Self {
+            config,
+            cells: HashMap::default(),
+            entities: vec![],
+            checks: AtomicUsize::new(0),
+        }
+    }
+
+    fn cell(&self, point: Vec2) -> (i32, i32) {
+        let cell = (point / self.config.cell_size).floor();
+        (cell.x as i32, cell.y as i32)
+    }
+
+    // Empty the cells but hang on to them so they don't need allocating
+    // again next frame
+    fn clear(&mut self) {
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+        self.entities.clear();
+    }
+
+    fn insert(&mut self, entity: Entity, min: Vec2, max: Vec2) {
+        self.entities.push(entity);
+
+        if !self.config.enabled {
+            return;
+        }
+
+        let (min_x, min_y) = self.cell(min);
+        let (max_x, max_y) = self.cell(max);
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                self.cells.entry((x, y)).or_default().push(entity);
+            }
+        }
+    }
+
+    // Entities in any cell touched by the box from `min` to `max`, these
+    // still need testing properly. Every entity in the grid is returned when
+    // it is disabled.
+    pub fn query(&self, min: Vec2, max: Vec2) -> Vec<Entity> {
+        let found = if self.config.enabled {
+            let (min_x, min_y) = self.cell(min);
+            let (max_x, max_y) = self.cell(max);
+
+            let mut found = vec![];
+            for x in min_x..=max_x {
+                for y in min_y..=max_y {
+                    found.extend(self.cells.get(&(x, y)).into_iter().flatten());
+                }
+            }
+            // Large entities can be in several cells
+            found.sort_unstable();
+            found.dedup();
+            found
+        } else {
+            self.entities.clone()
+        };
+
+        self.checks.fetch_add(found.len(), Ordering::Relaxed);
+        found
+    }
+
+    pub fn query_circle(&self, center: Vec2, radius: f32) -> Vec<Entity> {
+        self.query(center - Vec2::splat(radius), center + Vec2::splat(radius))
+    }
+
+    // Entities near a circle moving from `start` to `end`
+    pub fn query_swept(&self, start: Vec2, end: Vec2, radius: f32) -> Vec<Entity> {
+        self.query(
+            start.min(end) - Vec2::splat(radius),
+            start.max(end) + Vec2::splat(radius),
+        )
+    }
+}
+
+pub fn setup_collision_diagnostics(mut diagnostics: ResMut<Diagnostics>) {
+    diagnostics.add(Diagnostic::new(COLLISION_CHECKS, "collision_checks", 20));
+}
+
+// Explosions are never looked up in the grid, they only ever search it
+pub fn rebuild_spatial_grid(
+    mut grid: ResMut<SpatialGrid>,
+    mut diagnostics: ResMut<Diagnostics>,
+    circles: Query<
+        (
+            Entity,
+            &Transform,
+            &CircleCollider,
+            Option<&PreviousPosition>,
+        ),
+        Without<Explosion>,
+    >,
+    boxes: Query<(Entity, &Transform, &AabbCollider)>,
+) {
+    let checks = grid.checks.swap(0, Ordering::Relaxed);
+    diagnostics.add_measurement(COLLISION_CHECKS, checks as f64);
+
+    grid.clear();
+
+    for (entity, transform, collider, previous) in circles.iter() {
+        let start = start_position(transform, previous);
+        let end = transform.translation.truncate();
+        let radius = Vec2::splat(collider.0);
+
+        grid.insert(entity, start.min(end) - radius, start.max(end) + radius);
+    }
+
+    for (entity, transform, collider) in boxes.iter() {
+        let center = transform.translation.truncate() + collider.offset;
+
+        grid.insert(
+            entity,
+            center - collider.half_extents,
+            center + collider.half_extents,
+        );
+    }
+}
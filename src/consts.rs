@@ -1,17 +1,61 @@
 pub const PLAYER_MISSILE_VELOCITY: f32 = 200.0;
 pub const ENEMY_MISSILE_VELOCITY: f32 = 120.0;
 pub const MISSILE_RADIUS: f32 = 3.5;
+pub const TARGET_REACHED_RADIUS: f32 = 3.0; // Distance from the target a missile explodes at
+
+pub const MIRV_WARHEADS: usize = 3; // Number of missiles a MIRV splits into
+                                    // Height above the ground a MIRV splits at, as a fraction of the window height
+pub const MIRV_MIN_SPLIT_HEIGHT: f32 = 0.35;
+pub const MIRV_MAX_SPLIT_HEIGHT: f32 = 0.6;
+pub const MIRV_SCALE: f32 = 1.5;
+
+pub const SMART_BOMB_AVOID_DISTANCE: f32 = 48.0; // Distance from an explosion's edge to start dodging
+pub const SMART_BOMB_TURN_RATE: f32 = 3.0; // Radians per second
 
 pub const SILO_RELOAD_TIME: f32 = 3.0;
 pub const SILO_MAX_MISSILES: u8 = 3;
+// Level layouts, as fractions of the window width
+pub const LEVEL_MIN_GAP: f32 = 0.04; // Closest two structures can be to each other
+pub const LEVEL_EDGE_MARGIN: f32 = 0.03; // Closest a structure can be to the edge of the window
+                                         // Smart fire picks the silo that reaches the cursor soonest, these are in seconds
+pub const SMART_FIRE_MISSILE_COST: f32 = 0.15; // Added for each missile a silo is short of full
+pub const SMART_FIRE_THREAT_COST: f32 = 0.25; // Taken off for each enemy missile aimed at a silo
+pub const SMART_FIRE_THREAT_RADIUS: f32 = 48.0; // Distance from a silo an enemy target counts as aimed at it
 
 pub const EXPLOSION_SIZE: f32 = 32.0;
 
 pub const POWERUP_RADIUS: f32 = 16.0;
 
+pub const FLIER_RADIUS: f32 = 14.0;
+pub const FLIER_SPAWN_TIME: f32 = 15.0; // Seconds between each bomber/satellite
+pub const BOMBER_SPEED: f32 = 60.0;
+pub const BOMBER_DROP_TIME: f32 = 2.5; // Seconds between each dropped missile
+pub const SATELLITE_SPEED: f32 = 90.0;
+pub const SATELLITE_DROP_TIME: f32 = 3.5;
+
+pub const BOSS_HEALTH: u32 = 12; // Explosion hits to destroy the boss
+pub const BOSS_RADIUS: f32 = 36.0;
+pub const BOSS_SPEED: f32 = 30.0;
+pub const BOSS_BARRAGE_TIME: f32 = 4.0; // Seconds between each barrage
+
+pub const HEALTH_BAR_WIDTH: f32 = 64.0;
+pub const HEALTH_BAR_HEIGHT: f32 = 6.0;
+pub const HEALTH_BAR_OFFSET: f32 = 28.0; // Distance above the owner's centre
+
+pub const CHAIN_EXPLOSION_SIZE: f32 = 0.75; // Size of explosions set off by destroyed missiles
+pub const COMBO_TIME: f32 = 1.5; // Seconds without a chain link before the combo ends
+pub const MAX_COMBO_MULTIPLIER: u32 = 8;
+
+// Seconds the sky must be empty of enemy missiles before a wave is cleared
+pub const WAVE_CLEAR_DELAY: f32 = 1.0;
+
 // Score values
 pub const MISSILE_VALUE: usize = 10; // Standard destroyed missile
 pub const MISSILE_HIT_VALUE: usize = 20; // Missile destroyed by direct hit
+pub const SMART_BOMB_VALUE: usize = 50;
+pub const BOMBER_VALUE: usize = 100;
+pub const SATELLITE_VALUE: usize = 150;
+pub const BOSS_VALUE: usize = 1000;
 pub const SCORE_POWERUP_VALUE: usize = 100;
 
 // PowerUp values
@@ -19,3 +63,12 @@ pub const EXPLOSION_SIZE_SCALE: f32 = 2.0; // Player missile explosion size bonu
 pub const EXPLOSION_SIZE_TIME: f32 = 10.0; // Player missile explosion duration
 pub const MISSILE_SPEED_BONUS: f32 = 2.0; // Player missile speed bonus
 pub const MISSILE_SPEED_TIME: f32 = 10.0; // Player missile speed duration
+pub const HOMING_TIME: f32 = 10.0; // Player missile homing duration
+pub const HOMING_CONE: f32 = 0.6; // Radians either side of the missile's heading
+pub const HOMING_RANGE: f32 = 240.0;
+pub const HOMING_TURN_RATE: f32 = 4.0; // Radians per second
+
+// Stress test
+pub const STRESS_EXPLOSION_TIME: f32 = 0.1; // Seconds between each batch of explosions
+pub const STRESS_EXPLOSIONS: usize = 4; // Explosions in each batch
+pub const STRESS_COMPARE_TIME: f32 = 5.0; // Seconds between turning the spatial grid on and off
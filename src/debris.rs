@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{AssetHandles, GameState};
+use crate::{ground::GroundLevel, texture_height, AssetHandles, GameState};
 
 #[derive(Debug, Clone, Copy)]
 pub enum DebrisType {
@@ -24,20 +24,28 @@ impl Plugin for DebrisPlugin {
     }
 }
 
+// Debris sits on the ground where the structure stood
 fn spawn_debris(
     mut commands: Commands,
     asset_handles: Res<AssetHandles>,
+    ground_level: Res<GroundLevel>,
+    color_mats: Res<Assets<ColorMaterial>>,
+    textures: Res<Assets<Texture>>,
     mut events: EventReader<SpawnDebris>,
 ) {
     for e in events.iter() {
+        let material = match e.debris_type {
+            DebrisType::Building => asset_handles.debris_01.clone(),
+            DebrisType::Silo => asset_handles.silo_debris_01.clone(),
+        };
+        let y = ground_level.height_at(e.x_position)
+            + texture_height(&material, &color_mats, &textures) / 2.0;
+
         commands
             .spawn_bundle(SpriteBundle {
-                material: match e.debris_type {
-                    DebrisType::Building => asset_handles.debris_01.clone(),
-                    DebrisType::Silo => asset_handles.silo_debris_01.clone(),
-                },
+                material,
                 transform: Transform {
-                    translation: Vec3::new(e.x_position, -280.0, 0.0),
+                    translation: Vec3::new(e.x_position, y, 0.0),
                     ..Default::default()
                 },
                 ..Default::default()
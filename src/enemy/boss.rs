@@ -0,0 +1,214 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use crate::{
+    audio::PlayAudio,
+    collision::CircleCollider,
+    consts::{BOSS_BARRAGE_TIME, BOSS_HEALTH, BOSS_RADIUS, BOSS_SPEED, BOSS_VALUE},
+    debris::DebrisType,
+    explosion::SpawnExplosion,
+    game_status::UpdateScore,
+    missile::{MissileType, SpawnMissile},
+    rng::GameRng,
+    team::{CollisionLayers, Team},
+    AssetHandles, SpawnBuilding, Velocity,
+};
+
+use super::{
+    enemy_spawner::EnemyMissileSpawner,
+    health::{spawn_health_bar, Health, HealthBar},
+    Targeting, WaveStarted, Waves,
+};
+
+// Large mothership that patrols the top of the screen firing barrages of
+// missiles until it is shot down
+pub struct Boss {
+    barrage_timer: Timer,
+    next_barrage: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Barrage {
+    // Missiles fanning out from the boss across the ground below it
+    Fan,
+    // Missiles dropped along the length of the hull
+    Rain,
+    // A few missiles aimed at separate structures
+    Aimed,
+}
+
+const BARRAGES: [Barrage; 3] = [Barrage::Fan, Barrage::Rain, Barrage::Aimed];
+
+pub fn spawn_boss(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    waves: Res<Waves>,
+    asset_handles: Res<AssetHandles>,
+    mut wave_events: EventReader<WaveStarted>,
+) {
+    for e in wave_events.iter() {
+        if !waves.get(e.0 - 1).boss {
+            continue;
+        }
+
+        let window = windows.get_primary().unwrap();
+        let position = Vec3::new(
+            -window.width() / 2.0 - BOSS_RADIUS,
+            window.height() / 2.0 - BOSS_RADIUS * 2.0,
+            0.0,
+        );
+
+        let boss = commands
+            .spawn_bundle(SpriteBundle {
+                material: asset_handles.boss.clone(),
+                transform: Transform {
+                    translation: position,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .insert(Boss {
+                barrage_timer: Timer::from_seconds(BOSS_BARRAGE_TIME, true),
+                next_barrage: 0,
+            })
+            .insert(Health::new(BOSS_HEALTH))
+            .insert(Velocity(Vec2::new(BOSS_SPEED, 0.0)))
+            .insert(CircleCollider(BOSS_RADIUS))
+            .insert(CollisionLayers::new(
+                CollisionLayers::ENEMY_CRAFT,
+                CollisionLayers::NONE,
+            ))
+            .id();
+
+        spawn_health_bar(&mut commands, &asset_handles, boss, position);
+    }
+}
+
+// The boss enters from the left and then turns around at each edge of the
+// screen
+pub fn move_boss(windows: Res<Windows>, mut query: Query<(&Transform, &mut Velocity), With<Boss>>) {
+    let edge = windows.get_primary().unwrap().width() / 2.0 - BOSS_RADIUS;
+
+    for (transform, mut velocity) in query.iter_mut() {
+        let x = transform.translation.x;
+        if x > edge && velocity.0.x > 0.0 || x < -edge && velocity.0.x < 0.0 {
+            velocity.0.x = -velocity.0.x;
+        }
+    }
+}
+
+pub fn fire_barrages(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    enemy_spawner: Res<EnemyMissileSpawner>,
+    targeting: Res<Targeting>,
+    mut game_rng: ResMut<GameRng>,
+    mut query: Query<(&mut Boss, &Transform)>,
+    mut events: EventWriter<SpawnMissile>,
+) {
+    let half_width = windows.get_primary().unwrap().width() / 2.0;
+    let rng = game_rng.stream("boss");
+
+    for (mut boss, transform) in query.iter_mut() {
+        // Hold fire until the boss is on screen
+        if transform.translation.x.abs() > half_width {
+            continue;
+        }
+
+        if !boss.barrage_timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let barrage = BARRAGES[boss.next_barrage % BARRAGES.len()];
+        boss.next_barrage += 1;
+        debug!("Boss barrage: {:?}", barrage);
+
+        let position = transform.translation - Vec3::new(0.0, BOSS_RADIUS / 2.0, 0.0);
+        let shots: Vec<(Vec3, Vec3)> = match barrage {
+            Barrage::Fan => (0..5)
+                .map(|i| {
+                    let x = position.x + (i as f32 - 2.0) * half_width * 0.3;
+                    let x = x.clamp(-half_width, half_width);
+                    (position, targeting.ground_at(x))
+                })
+                .collect(),
+            Barrage::Rain => (0..4)
+                .map(|i| {
+                    let offset = Vec3::new((i as f32 - 1.5) * BOSS_RADIUS * 0.6, 0.0, 0.0);
+                    (position + offset, targeting.pick(rng))
+                })
+                .collect(),
+            Barrage::Aimed => targeting
+                .pick_many(3, rng)
+                .into_iter()
+                .map(|target| (position, target))
+                .collect(),
+        };
+
+        events.send_batch(shots.into_iter().map(|(position, target)| SpawnMissile {
+            position,
+            target,
+            team: Team::Enemy,
+            missile_type: MissileType::Standard,
+            speed: enemy_spawner.speed,
+            marker: None,
+        }));
+    }
+}
+
+// Blow up any boss that has run out of health. As a reward one destroyed
+// building is rebuilt.
+pub fn check_boss_defeated(
+    mut commands: Commands,
+    asset_handles: Res<AssetHandles>,
+    mut game_rng: ResMut<GameRng>,
+    bosses: Query<(Entity, &Health, &Transform), With<Boss>>,
+    debris: Query<(Entity, &DebrisType, &Transform)>,
+    mut score_events: EventWriter<UpdateScore>,
+    mut explosion_events: EventWriter<SpawnExplosion>,
+    mut building_events: EventWriter<SpawnBuilding>,
+    mut audio_events: EventWriter<PlayAudio>,
+) {
+    for (entity, health, transform) in bosses.iter() {
+        if !health.is_dead() {
+            continue;
+        }
+
+        commands.entity(entity).despawn();
+        score_events.send(UpdateScore(BOSS_VALUE));
+        audio_events.send(PlayAudio {
+            handle: asset_handles.explosion_audio.clone(),
+        });
+
+        for x in [-1.0, 0.0, 1.0].iter() {
+            explosion_events.send(SpawnExplosion {
+                position: transform.translation + Vec3::new(x * BOSS_RADIUS, 0.0, 0.0),
+                team: Team::Enemy,
+                size: 1.5,
+                chain: 0,
+            });
+        }
+
+        let ruins: Vec<(Entity, &Transform)> = debris
+            .iter()
+            .filter(|(_, debris_type, _)| matches!(debris_type, DebrisType::Building))
+            .map(|(entity, _, transform)| (entity, transform))
+            .collect();
+
+        if let Some((ruin, ruin_transform)) = ruins.choose(game_rng.stream("boss_defeated")) {
+            commands.entity(*ruin).despawn();
+            building_events.send(SpawnBuilding {
+                x_position: ruin_transform.translation.x,
+            });
+        }
+    }
+}
+
+pub fn despawn_boss(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<Boss>, With<HealthBar>)>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs::File;
+
+use bevy::prelude::*;
+use ron::de::from_reader;
+use serde::Deserialize;
+
+use super::enemy_spawner::EnemyMissileSpawner;
+
+// What the difficulty curve is measured against
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum Progress {
+    // The current wave number, starting from 1
+    Wave,
+    // Seconds spent in the game, not counting time paused
+    Time,
+}
+
+// A point on the difficulty curve, each value is a multiplier on top of the
+// numbers set in `waves.ron`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Keyframe {
+    pub at: f32,
+    pub speed: f32,
+    pub spawn_rate: f32,
+    pub event_rate: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DifficultyCurve {
+    pub progress: Progress,
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl DifficultyCurve {
+    pub fn load(path: &str) -> Self {
+        let f = File::open(path).expect("Could not open file");
+
+        let mut curve: DifficultyCurve = match from_reader(f) {
+            Ok(x) => x,
+            Err(e) => {
+                println!("Failed to load difficulty: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        if curve.keyframes.is_empty() {
+            println!("Failed to load difficulty: {} contains no keyframes", path);
+            std::process::exit(1);
+        }
+        if curve.keyframes.iter().any(|k| !k.at.is_finite()) {
+            println!(
+                "Failed to load difficulty: {} has a keyframe that isn't a number",
+                path
+            );
+            std::process::exit(1);
+        }
+        curve.keyframes.sort_by(|a, b| a.at.total_cmp(&b.at));
+
+        curve
+    }
+
+    // Linearly blend between the keyframes either side of `at`. Before the
+    // first keyframe and after the last the curve stays flat.
+    pub fn sample(&self, at: f32) -> (f32, f32, f32) {
+        let first = &self.keyframes[0];
+        let last = &self.keyframes[self.keyframes.len() - 1];
+
+        let (from, to) = if at <= first.at {
+            (first, first)
+        } else if at >= last.at {
+            (last, last)
+        } else {
+            let i = self.keyframes.iter().position(|k| k.at > at).unwrap();
+            (&self.keyframes[i - 1], &self.keyframes[i])
+        };
+
+        let t = if to.at > from.at {
+            (at - from.at) / (to.at - from.at)
+        } else {
+            0.0
+        };
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+        (
+            lerp(from.speed, to.speed),
+            lerp(from.spawn_rate, to.spawn_rate),
+            lerp(from.event_rate, to.event_rate),
+        )
+    }
+}
+
+// Resource holding the current difficulty, read by the enemy spawner and when
+// spawning enemy missiles
+#[derive(Debug)]
+pub struct Difficulty {
+    pub curve: DifficultyCurve,
+    pub elapsed: f32,
+    // Multiplier for enemy missile speed
+    pub speed: f32,
+    // Multiplier for how quickly wave missiles are launched
+    pub spawn_rate: f32,
+    // Multiplier for how often enemy events are triggered
+    pub event_rate: f32,
+}
+
+impl Difficulty {
+    pub fn new(curve: DifficultyCurve) -> Self {
+        let mut difficulty = Self {
+            curve,
+            elapsed: 0.0,
+            speed: 1.0,
+            spawn_rate: 1.0,
+            event_rate: 1.0,
+        };
+        difficulty.update(1);
+        difficulty
+    }
+
+    fn update(&mut self, wave: usize) {
+        let at = match self.curve.progress {
+            Progress::Wave => wave.max(1) as f32,
+            Progress::Time => self.elapsed,
+        };
+
+        let (speed, spawn_rate, event_rate) = self.curve.sample(at);
+        self.speed = speed.max(0.0);
+        self.spawn_rate = spawn_rate.max(0.0);
+        self.event_rate = event_rate.max(0.0);
+    }
+}
+
+pub fn update_difficulty(
+    time: Res<Time>,
+    spawner: Res<EnemyMissileSpawner>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    difficulty.elapsed += time.delta_seconds();
+    difficulty.update(spawner.wave);
+}
+
+pub fn reset_difficulty(mut difficulty: ResMut<Difficulty>) {
+    difficulty.elapsed = 0.0;
+    difficulty.update(1);
+}
@@ -0,0 +1,170 @@
+use std::fs::File;
+
+use bevy::prelude::*;
+use ron::de::from_reader;
+use serde::Deserialize;
+
+use crate::{
+    consts::SILO_RELOAD_TIME,
+    game_status::{GameStats, GameStatus},
+    silo::Wrecked,
+    Silo,
+};
+
+use super::enemy_spawner::{EnemyMissileSpawner, WaveState};
+
+#[derive(Debug, Deserialize)]
+pub struct DirectorConfig {
+    pub enabled: bool,
+    // Seconds between each check of how the player is doing
+    pub sample_interval: f32,
+    // How far each new sample moves the tension towards itself, 0 - 1
+    pub smoothing: f32,
+    // Band of tension the director tries to keep the game in
+    pub target_min: f32,
+    pub target_max: f32,
+    // Pressure change each sample while the tension is outside the band
+    pub pressure_step: f32,
+    pub min_pressure: f32,
+    pub max_pressure: f32,
+    // How much each signal adds to the tension
+    pub landed_weight: f32,
+    pub building_lost_weight: f32,
+    pub silo_lost_weight: f32,
+    pub ammo_weight: f32,
+}
+
+impl DirectorConfig {
+    pub fn load(path: &str) -> Self {
+        let f = File::open(path).expect("Could not open file");
+
+        let config: DirectorConfig = match from_reader(f) {
+            Ok(x) => x,
+            Err(e) => {
+                println!("Failed to load director: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        if !config.sample_interval.is_finite() || config.sample_interval <= 0.0 {
+            println!(
+                "Failed to load director: sample_interval must be above 0, not {}",
+                config.sample_interval
+            );
+            std::process::exit(1);
+        }
+        let pressures = [config.min_pressure, config.max_pressure];
+        if pressures.iter().any(|p| !p.is_finite()) || config.min_pressure > config.max_pressure {
+            println!(
+                "Failed to load director: pressure range {} - {} isn't valid",
+                config.min_pressure, config.max_pressure
+            );
+            std::process::exit(1);
+        }
+
+        config
+    }
+}
+
+// Resource that watches how the player is doing and speeds up or slows down
+// the enemy spawner to keep the game tense without overwhelming them.
+//
+// `tension` is a 0 - 1 estimate of how hard the player is finding things,
+// built from the share of enemy missiles getting through, structures lost and
+// how hard the player is having to fire. `pressure` multiplies the spawner timers.
+#[derive(Debug)]
+pub struct Director {
+    pub config: DirectorConfig,
+    pub tension: f32,
+    pub pressure: f32,
+    timer: Timer,
+    last_stats: GameStats,
+}
+
+impl Director {
+    pub fn new(config: DirectorConfig) -> Self {
+        Self {
+            tension: (config.target_min + config.target_max) / 2.0,
+            pressure: 1.0,
+            timer: Timer::from_seconds(config.sample_interval, true),
+            last_stats: GameStats::default(),
+            config,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.tension = (self.config.target_min + self.config.target_max) / 2.0;
+        self.pressure = 1.0;
+        self.timer.reset();
+        self.last_stats = GameStats::default();
+    }
+
+    fn sample_tension(&self, stats: &GameStats, ammo_used: f32) -> f32 {
+        let config = &self.config;
+
+        let faced = stats.intercepted + stats.landed;
+        let landed = if faced > 0 {
+            stats.landed as f32 / faced as f32
+        } else {
+            0.0
+        };
+
+        (config.landed_weight * landed
+            + config.building_lost_weight * stats.buildings_lost as f32
+            + config.silo_lost_weight * stats.silos_lost as f32
+            + config.ammo_weight * ammo_used)
+            .clamp(0.0, 1.0)
+    }
+}
+
+pub fn run_director(
+    time: Res<Time>,
+    game_status: Res<GameStatus>,
+    spawner: Res<EnemyMissileSpawner>,
+    mut director: ResMut<Director>,
+    silos: Query<&Silo, Without<Wrecked>>,
+) {
+    // Nothing happens between waves so don't let it count as the game being
+    // too easy
+    if !director.config.enabled || spawner.state == WaveState::Intermission {
+        return;
+    }
+
+    if director.timer.tick(time.delta()).just_finished() {
+        let stats = game_status.stats.since(&director.last_stats);
+        director.last_stats = game_status.stats;
+
+        // Share of the missiles the standing silos could have reloaded during
+        // the sample that the player actually fired
+        let reloads =
+            silos.iter().count() as f32 * director.config.sample_interval / SILO_RELOAD_TIME;
+        let ammo_used = if reloads > 0.0 {
+            (stats.missiles_fired as f32 / reloads).min(1.0)
+        } else {
+            1.0
+        };
+
+        let sample = director.sample_tension(&stats, ammo_used);
+        let smoothing = director.config.smoothing.clamp(0.0, 1.0);
+        director.tension += (sample - director.tension) * smoothing;
+
+        let step = director.config.pressure_step;
+        if director.tension < director.config.target_min {
+            director.pressure += step;
+        } else if director.tension > director.config.target_max {
+            director.pressure -= step;
+        }
+        director.pressure = director
+            .pressure
+            .clamp(director.config.min_pressure, director.config.max_pressure);
+
+        debug!(
+            "Director tension: {:.2}, pressure: {:.2}",
+            director.tension, director.pressure
+        );
+    }
+}
+
+pub fn reset_director(mut director: ResMut<Director>) {
+    director.reset();
+}
@@ -1,27 +1,164 @@
 use bevy::prelude::*;
-use rand::prelude::*;
+use rand::{prelude::*, rngs::StdRng};
 
-use crate::{missile::SpawnMissile, team::Team};
+use crate::{
+    missile::{MissileType, SpawnMissile},
+    team::Team,
+};
 
-// TODO - Add more events and choose one at random
+use super::Targeting;
 
-pub fn multiple_missiles(
-    half_width: f32,
-    half_height: f32,
-    rng: &mut ThreadRng,
-) -> Vec<SpawnMissile> {
+// Vertical gap between missiles in a staggered stream
+const STREAM_SPACING: f32 = 60.0;
+// Horizontal spread of missiles aimed at the same silo
+const FOCUSED_STRIKE_SPREAD: f32 = 24.0;
+
+// Everything an enemy event needs to know about the current game
+pub struct EnemyEventContext<'a> {
+    pub half_width: f32,
+    pub half_height: f32,
+    pub speed: f32,
+    pub silos: Vec<Vec3>,
+    pub targeting: &'a Targeting,
+}
+
+pub type EnemyEventFn = fn(&EnemyEventContext, &mut StdRng) -> Vec<SpawnMissile>;
+
+pub struct EnemyEvent {
+    pub name: &'static str,
+    pub weight: f32,
+    pub spawn: EnemyEventFn,
+}
+
+// Resource holding every enemy event that can be chosen when the enemy
+// event timer finishes
+#[derive(Default)]
+pub struct EnemyEvents {
+    events: Vec<EnemyEvent>,
+}
+
+impl EnemyEvents {
+    pub fn add(&mut self, name: &'static str, weight: f32, spawn: EnemyEventFn) -> &mut Self {
+        self.events.push(EnemyEvent {
+            name,
+            weight,
+            spawn,
+        });
+        self
+    }
+
+    // Choose an event at random, events with a higher weight are more likely
+    pub fn choose(&self, rng: &mut impl Rng) -> Option<&EnemyEvent> {
+        self.events.choose_weighted(rng, |e| e.weight).ok()
+    }
+}
+
+// Lets plugins register their own enemy events
+pub trait EnemyEventsExt {
+    fn add_enemy_event(
+        &mut self,
+        name: &'static str,
+        weight: f32,
+        spawn: EnemyEventFn,
+    ) -> &mut Self;
+}
+
+impl EnemyEventsExt for AppBuilder {
+    fn add_enemy_event(
+        &mut self,
+        name: &'static str,
+        weight: f32,
+        spawn: EnemyEventFn,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(EnemyEvents::default)
+            .add(name, weight, spawn);
+        self
+    }
+}
+
+fn enemy_missile(position: Vec3, target: Vec3, speed: f32) -> SpawnMissile {
+    SpawnMissile {
+        position,
+        target,
+        team: Team::Enemy,
+        missile_type: MissileType::Standard,
+        speed,
+        marker: None,
+    }
+}
+
+// A few missiles launched at the same time from random positions
+pub fn multiple_missiles(ctx: &EnemyEventContext, rng: &mut StdRng) -> Vec<SpawnMissile> {
     let mut to_spawn = vec![];
 
     for _ in 0..3 {
-        let x_pos = rng.gen_range(-half_width..half_width);
-        let x_tar = rng.gen_range(-half_width..half_width);
+        let x_pos = rng.gen_range(-ctx.half_width..ctx.half_width);
 
-        to_spawn.push(SpawnMissile {
-            position: Vec3::new(x_pos, half_height, 0.0),
-            target: Vec3::new(x_tar, -half_height, 0.0),
-            team: Team::Enemy,
-        });
+        to_spawn.push(enemy_missile(
+            Vec3::new(x_pos, ctx.half_height, 0.0),
+            ctx.targeting.pick(rng),
+            ctx.speed,
+        ));
     }
 
     to_spawn
 }
+
+// A line of missiles following each other down the same path. The missiles
+// start above the screen so they arrive one after another.
+pub fn staggered_stream(ctx: &EnemyEventContext, rng: &mut StdRng) -> Vec<SpawnMissile> {
+    let x_pos = rng.gen_range(-ctx.half_width..ctx.half_width);
+    let target = ctx.targeting.pick(rng);
+
+    (0..5)
+        .map(|i| {
+            enemy_missile(
+                Vec3::new(x_pos, ctx.half_height + STREAM_SPACING * i as f32, 0.0),
+                target,
+                ctx.speed,
+            )
+        })
+        .collect()
+}
+
+// Missiles enter from both edges of the screen, each side aims at targets on
+// the opposite half of the ground so their paths cross
+pub fn pincer(ctx: &EnemyEventContext, rng: &mut StdRng) -> Vec<SpawnMissile> {
+    let mut to_spawn = vec![];
+    let targets = ctx.targeting.pick_many(4, rng);
+
+    for target in targets {
+        let side = if target.x > 0.0 { -1.0 } else { 1.0 };
+        let y_pos = rng.gen_range(0.0..ctx.half_height);
+
+        to_spawn.push(enemy_missile(
+            Vec3::new(ctx.half_width * side, y_pos, 0.0),
+            target,
+            ctx.speed,
+        ));
+    }
+
+    to_spawn
+}
+
+// Several missiles aimed at a single silo
+pub fn focused_strike(ctx: &EnemyEventContext, rng: &mut StdRng) -> Vec<SpawnMissile> {
+    let x_tar = match ctx.silos.choose(rng) {
+        Some(silo) => silo.x,
+        None => rng.gen_range(-ctx.half_width..ctx.half_width),
+    };
+
+    (0..4)
+        .map(|_| {
+            let x_pos = rng.gen_range(-ctx.half_width..ctx.half_width);
+            let spread = rng.gen_range(-FOCUSED_STRIKE_SPREAD..FOCUSED_STRIKE_SPREAD);
+
+            enemy_missile(
+                Vec3::new(x_pos, ctx.half_height, 0.0),
+                Vec3::new(x_tar + spread, -ctx.half_height, 0.0),
+                ctx.speed,
+            )
+        })
+        .collect()
+}
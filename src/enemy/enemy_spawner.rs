@@ -1,41 +1,164 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::Duration};
 use rand::prelude::*;
 
-use crate::{missile::SpawnMissile, team::Team};
+use crate::{
+    consts::WAVE_CLEAR_DELAY,
+    missile::{Missile, MissileType, SpawnMissile},
+    rng::GameRng,
+    silo::Wrecked,
+    team::Team,
+    Silo,
+};
 
-use super::multiple_missiles;
+use super::{boss::Boss, Difficulty, Director, EnemyEventContext, EnemyEvents, Targeting, Waves};
+
+// Events
+pub struct WaveStarted(pub usize);
+pub struct WaveCleared(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WaveState {
+    // Waiting for the next wave to start
+    Intermission,
+    // Missiles from the current wave are still being spawned
+    Spawning,
+    // Every missile has been spawned, waiting for them to be destroyed
+    Clearing,
+}
 
 pub struct EnemyMissileSpawner {
+    pub wave: usize, // Number of the current wave, starting from 1
+    pub state: WaveState,
+    pub queue: Vec<MissileType>,
+    pub speed: f32,
     pub timer: Timer,
-    pub enemy_event_timer: Timer,
+    pub enemy_event_timer: Option<Timer>,
+    pub intermission_timer: Timer,
+    pub clear_timer: Timer,
+}
+
+impl EnemyMissileSpawner {
+    pub fn new(waves: &Waves) -> Self {
+        Self {
+            wave: 0,
+            state: WaveState::Intermission,
+            queue: vec![],
+            speed: 0.0,
+            timer: Timer::new(Duration::from_secs_f32(1.0), true),
+            enemy_event_timer: None,
+            intermission_timer: Timer::new(Duration::from_secs_f32(waves.intermission), false),
+            clear_timer: Timer::new(Duration::from_secs_f32(WAVE_CLEAR_DELAY), false),
+        }
+    }
+
+    fn start_wave(&mut self, waves: &Waves) {
+        self.wave += 1;
+        let wave = waves.get(self.wave - 1);
+
+        // Reverse the queue so missiles can be popped off the end in order
+        self.queue = wave.missile_queue();
+        self.queue.reverse();
+        self.speed = wave.speed;
+        self.timer = Timer::new(Duration::from_secs_f32(wave.interval), true);
+        self.enemy_event_timer = wave
+            .event_interval
+            .map(|interval| Timer::new(Duration::from_secs_f32(interval), true));
+        self.state = WaveState::Spawning;
+    }
 }
 
 pub fn update_timer(
     time: Res<Time>,
     windows: Res<Windows>,
+    waves: Res<Waves>,
+    enemy_events: Res<EnemyEvents>,
+    targeting: Res<Targeting>,
+    difficulty: Res<Difficulty>,
+    director: Res<Director>,
+    mut game_rng: ResMut<GameRng>,
     mut spawner: ResMut<EnemyMissileSpawner>,
+    missiles: Query<&Team, With<Missile>>,
+    bosses: Query<&Boss>,
+    silos: Query<&Transform, (With<Silo>, Without<Wrecked>)>,
     mut events: EventWriter<SpawnMissile>,
+    mut wave_started_events: EventWriter<WaveStarted>,
+    mut wave_cleared_events: EventWriter<WaveCleared>,
 ) {
     let (half_width, half_height) = if let Some(window) = windows.get_primary() {
         (window.width() / 2.0, window.height() / 2.0)
     } else {
         panic!("Could not get primary window!");
     };
-    let mut rng = thread_rng();
+    let rng = game_rng.stream("enemy_spawner");
 
-    if spawner.timer.tick(time.delta()).finished() {
-        let x_pos = rng.gen_range(-half_width..half_width);
-        let x_tar = rng.gen_range(-half_width..half_width);
+    // Higher difficulty and director pressure run the spawn timers faster
+    let spawn_delta = time
+        .delta()
+        .mul_f32(difficulty.spawn_rate * director.pressure);
+    let event_delta = time
+        .delta()
+        .mul_f32(difficulty.event_rate * director.pressure);
 
-        events.send(SpawnMissile {
-            position: Vec3::new(x_pos, half_height, 0.0),
-            target: Vec3::new(x_tar, -half_height, 0.0),
-            team: Team::Enemy,
-        });
-    }
+    match spawner.state {
+        WaveState::Intermission => {
+            if spawner.intermission_timer.tick(time.delta()).finished() {
+                spawner.start_wave(&waves);
+                wave_started_events.send(WaveStarted(spawner.wave));
+            }
+        }
+        WaveState::Spawning => {
+            if spawner.timer.tick(spawn_delta).finished() {
+                if let Some(missile_type) = spawner.queue.pop() {
+                    let x_pos = rng.gen_range(-half_width..half_width);
+
+                    events.send(SpawnMissile {
+                        position: Vec3::new(x_pos, half_height, 0.0),
+                        target: targeting.pick(rng),
+                        team: Team::Enemy,
+                        missile_type,
+                        speed: spawner.speed,
+                        marker: None,
+                    });
+                }
+            }
+
+            let speed = spawner.speed;
+            if let Some(timer) = spawner.enemy_event_timer.as_mut() {
+                if timer.tick(event_delta).finished() {
+                    if let Some(event) = enemy_events.choose(rng) {
+                        debug!("Enemy event: {}", event.name);
 
-    if spawner.enemy_event_timer.tick(time.delta()).finished() {
-        let to_spawn = multiple_missiles(half_width, half_height, &mut rng);
-        events.send_batch(to_spawn.into_iter());
+                        let ctx = EnemyEventContext {
+                            half_width,
+                            half_height,
+                            speed,
+                            silos: silos.iter().map(|t| t.translation).collect(),
+                            targeting: &targeting,
+                        };
+                        events.send_batch((event.spawn)(&ctx, rng).into_iter());
+                    }
+                }
+            }
+
+            if spawner.queue.is_empty() {
+                spawner.clear_timer.reset();
+                spawner.state = WaveState::Clearing;
+            }
+        }
+        WaveState::Clearing => {
+            // Missiles spawned from events don't exist until the end of the
+            // frame, so wait until the sky has been empty for a short time.
+            if missiles.iter().any(|team| *team == Team::Enemy) || bosses.iter().next().is_some() {
+                spawner.clear_timer.reset();
+            } else if spawner.clear_timer.tick(time.delta()).finished() {
+                wave_cleared_events.send(WaveCleared(spawner.wave));
+                spawner.intermission_timer.reset();
+                spawner.state = WaveState::Intermission;
+            }
+        }
     }
 }
+
+pub fn reset_spawner(waves: Res<Waves>, mut spawner: ResMut<EnemyMissileSpawner>) {
+    *spawner = EnemyMissileSpawner::new(&waves);
+}
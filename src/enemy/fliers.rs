@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use crate::{
+    collision::CircleCollider,
+    consts::{
+        BOMBER_DROP_TIME, BOMBER_SPEED, BOMBER_VALUE, FLIER_RADIUS, SATELLITE_DROP_TIME,
+        SATELLITE_SPEED, SATELLITE_VALUE,
+    },
+    missile::{MissileType, SpawnMissile},
+    rng::GameRng,
+    team::{CollisionLayers, Team},
+    AssetHandles, Velocity,
+};
+
+use super::{
+    enemy_spawner::{EnemyMissileSpawner, WaveState},
+    Targeting,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlierType {
+    Bomber,
+    Satellite,
+}
+
+impl FlierType {
+    // Bonus score for shooting the flier down
+    pub fn value(&self) -> usize {
+        match self {
+            FlierType::Bomber => BOMBER_VALUE,
+            FlierType::Satellite => SATELLITE_VALUE,
+        }
+    }
+
+    fn speed(&self) -> f32 {
+        match self {
+            FlierType::Bomber => BOMBER_SPEED,
+            FlierType::Satellite => SATELLITE_SPEED,
+        }
+    }
+
+    fn drop_time(&self) -> f32 {
+        match self {
+            FlierType::Bomber => BOMBER_DROP_TIME,
+            FlierType::Satellite => SATELLITE_DROP_TIME,
+        }
+    }
+}
+
+// Enemy craft that crosses the sky dropping missiles
+pub struct Flier {
+    pub flier_type: FlierType,
+    drop_timer: Timer,
+}
+
+pub struct FlierSpawner {
+    pub timer: Timer,
+}
+
+// Spawn Flier Event
+pub struct SpawnFlier {
+    flier_type: FlierType,
+    position: Vec3,
+    velocity: Vec2,
+}
+
+// Fliers only show up while a wave is spawning so they don't keep dropping
+// missiles after it has been cleared
+pub fn run_flier_spawner(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    enemy_spawner: Res<EnemyMissileSpawner>,
+    mut game_rng: ResMut<GameRng>,
+    mut spawner: ResMut<FlierSpawner>,
+    mut events: EventWriter<SpawnFlier>,
+) {
+    if enemy_spawner.state != WaveState::Spawning {
+        return;
+    }
+
+    if spawner.timer.tick(time.delta()).finished() {
+        let (half_width, half_height) = (
+            windows.get_primary().unwrap().width() / 2.0,
+            windows.get_primary().unwrap().height() / 2.0,
+        );
+
+        let rng = game_rng.stream("flier_spawner");
+        let (flier_type, y) = match rng.gen_bool(0.5) {
+            true => (
+                FlierType::Bomber,
+                rng.gen_range(half_height * 0.35..half_height * 0.55),
+            ),
+            false => (
+                FlierType::Satellite,
+                rng.gen_range(half_height * 0.6..half_height * 0.8),
+            ),
+        };
+
+        let (x, direction) = match rng.gen_bool(0.5) {
+            true => (-half_width - FLIER_RADIUS, 1.0),
+            false => (half_width + FLIER_RADIUS, -1.0),
+        };
+
+        events.send(SpawnFlier {
+            flier_type,
+            position: Vec3::new(x, y, 0.0),
+            velocity: Vec2::new(flier_type.speed() * direction, 0.0),
+        });
+    }
+}
+
+pub fn spawn_fliers(
+    mut commands: Commands,
+    asset_handles: Res<AssetHandles>,
+    mut events: EventReader<SpawnFlier>,
+) {
+    for e in events.iter() {
+        commands
+            .spawn_bundle(SpriteBundle {
+                material: match e.flier_type {
+                    FlierType::Bomber => asset_handles.bomber.clone(),
+                    FlierType::Satellite => asset_handles.satellite.clone(),
+                },
+                sprite: Sprite {
+                    flip_x: e.velocity.x < 0.0,
+                    ..Default::default()
+                },
+                transform: Transform {
+                    translation: e.position,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .insert(Flier {
+                flier_type: e.flier_type,
+                drop_timer: Timer::from_seconds(e.flier_type.drop_time(), true),
+            })
+            .insert(Velocity(e.velocity))
+            .insert(CircleCollider(FLIER_RADIUS))
+            .insert(CollisionLayers::new(
+                CollisionLayers::ENEMY_CRAFT,
+                CollisionLayers::NONE,
+            ));
+    }
+}
+
+pub fn drop_missiles(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    enemy_spawner: Res<EnemyMissileSpawner>,
+    targeting: Res<Targeting>,
+    mut game_rng: ResMut<GameRng>,
+    mut query: Query<(&mut Flier, &Transform)>,
+    mut events: EventWriter<SpawnMissile>,
+) {
+    let half_width = windows.get_primary().unwrap().width() / 2.0;
+    let rng = game_rng.stream("drop_missiles");
+
+    for (mut flier, transform) in query.iter_mut() {
+        // Don't drop missiles while still off screen
+        if transform.translation.x.abs() > half_width {
+            continue;
+        }
+
+        if flier.drop_timer.tick(time.delta()).finished() {
+            events.send(SpawnMissile {
+                position: transform.translation,
+                target: targeting.pick(rng),
+                team: Team::Enemy,
+                missile_type: MissileType::Standard,
+                speed: enemy_spawner.speed,
+                marker: None,
+            });
+        }
+    }
+}
+
+pub fn check_offscreen_fliers(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    query: Query<(Entity, &Transform, &Velocity, &CircleCollider), With<Flier>>,
+) {
+    let half_width = windows.get_primary().unwrap().width() / 2.0;
+
+    for (entity, transform, velocity, collider) in query.iter() {
+        // Only despawn fliers that are moving away from the screen
+        let x = transform.translation.x;
+        if x > half_width + collider.0 && velocity.0.x > 0.0
+            || x < -half_width - collider.0 && velocity.0.x < 0.0
+        {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn despawn_fliers(mut commands: Commands, query: Query<(Entity, &Flier)>) {
+    for (entity, _) in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+
+use crate::{
+    consts::{HEALTH_BAR_HEIGHT, HEALTH_BAR_OFFSET, HEALTH_BAR_WIDTH},
+    AssetHandles,
+};
+
+// Event sent when an explosion touches an entity with Health
+pub struct Damage {
+    pub target: Entity,
+    pub source: Entity,
+}
+
+// Enemies with Health take several hits before being destroyed instead of
+// being despawned as soon as an explosion touches them. Explosions last for
+// several frames so each one can only do damage once.
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+    hit_by: Vec<Entity>,
+}
+
+impl Health {
+    pub fn new(max: u32) -> Self {
+        Self {
+            current: max,
+            max,
+            hit_by: vec![],
+        }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current == 0
+    }
+
+    pub fn fraction(&self) -> f32 {
+        self.current as f32 / self.max as f32
+    }
+
+    fn damage(&mut self, source: Entity) {
+        if !self.hit_by.contains(&source) {
+            self.hit_by.push(source);
+            self.current = self.current.saturating_sub(1);
+        }
+    }
+}
+
+// Bar shown above an entity with Health, it shrinks towards the left as the
+// owner takes damage
+pub struct HealthBar {
+    pub owner: Entity,
+}
+
+pub fn spawn_health_bar(
+    commands: &mut Commands,
+    asset_handles: &AssetHandles,
+    owner: Entity,
+    position: Vec3,
+) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                size: Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT),
+                ..Default::default()
+            },
+            material: asset_handles.health_bar.clone(),
+            transform: Transform {
+                translation: position + Vec3::new(0.0, HEALTH_BAR_OFFSET, 0.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(HealthBar { owner });
+}
+
+pub fn apply_damage(mut events: EventReader<Damage>, mut query: Query<&mut Health>) {
+    for e in events.iter() {
+        if let Ok(mut health) = query.get_mut(e.target) {
+            health.damage(e.source);
+        }
+    }
+}
+
+pub fn update_health_bars(
+    mut commands: Commands,
+    owners: Query<(&Health, &Transform), Without<HealthBar>>,
+    mut bars: Query<(Entity, &HealthBar, &mut Sprite, &mut Transform)>,
+) {
+    for (entity, bar, mut sprite, mut transform) in bars.iter_mut() {
+        match owners.get(bar.owner) {
+            Ok((health, owner_transform)) => {
+                sprite.size.x = HEALTH_BAR_WIDTH * health.fraction();
+
+                let x_offset = (sprite.size.x - HEALTH_BAR_WIDTH) / 2.0;
+                transform.translation =
+                    owner_transform.translation + Vec3::new(x_offset, HEALTH_BAR_OFFSET, 0.0);
+            }
+            Err(_) => commands.entity(entity).despawn(),
+        }
+    }
+}
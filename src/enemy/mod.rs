@@ -1,22 +1,102 @@
 use bevy::{prelude::*, utils::Duration};
 
-use crate::state::GameState;
+use crate::{consts::FLIER_SPAWN_TIME, state::GameState, stress};
 
+mod boss;
+mod difficulty;
+mod director;
 mod enemy_events;
 mod enemy_spawner;
+mod fliers;
+mod health;
+mod targeting;
+mod waves;
+
+pub use self::{
+    difficulty::Difficulty,
+    director::Director,
+    enemy_events::{EnemyEventContext, EnemyEvents, EnemyEventsExt},
+    enemy_spawner::{WaveCleared, WaveStarted},
+    fliers::{Flier, FlierType},
+    health::{Damage, Health},
+    targeting::Targeting,
+    waves::Waves,
+};
 
 use self::{
-    enemy_events::multiple_missiles,
-    enemy_spawner::{update_timer, EnemyMissileSpawner},
+    boss::{check_boss_defeated, despawn_boss, fire_barrages, move_boss, spawn_boss},
+    difficulty::{reset_difficulty, update_difficulty, DifficultyCurve},
+    director::{reset_director, run_director, DirectorConfig},
+    enemy_events::{focused_strike, multiple_missiles, pincer, staggered_stream},
+    enemy_spawner::{reset_spawner, update_timer, EnemyMissileSpawner},
+    fliers::{
+        check_offscreen_fliers, despawn_fliers, drop_missiles, run_flier_spawner, spawn_fliers,
+        FlierSpawner, SpawnFlier,
+    },
+    health::{apply_damage, update_health_bars},
+    targeting::update_targeting,
 };
 
 pub struct EnemySpawnerPlugin;
 impl Plugin for EnemySpawnerPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.insert_resource(EnemyMissileSpawner {
-            timer: Timer::new(Duration::from_secs_f32(3.0), true),
-            enemy_event_timer: Timer::new(Duration::from_secs_f32(12.0), true),
-        })
-        .add_system_set(SystemSet::on_update(GameState::Game).with_system(update_timer.system()));
+        // Load the waves from waves.ron, or stress_waves.ron when running the
+        // collision benchmark
+        let waves = if stress::enabled() {
+            Waves::load("./config/stress_waves.ron")
+        } else {
+            Waves::load("./config/waves.ron")
+        };
+        // Load the difficulty curve from difficulty.ron
+        let difficulty = Difficulty::new(DifficultyCurve::load("./config/difficulty.ron"));
+        // Load the adaptive difficulty settings from director.ron
+        let director = Director::new(DirectorConfig::load("./config/director.ron"));
+
+        app.add_event::<WaveStarted>()
+            .add_event::<WaveCleared>()
+            .add_event::<SpawnFlier>()
+            .add_event::<Damage>()
+            .insert_resource(EnemyMissileSpawner::new(&waves))
+            .insert_resource(FlierSpawner {
+                timer: Timer::new(Duration::from_secs_f32(FLIER_SPAWN_TIME), true),
+            })
+            .insert_resource(waves)
+            .insert_resource(difficulty)
+            .insert_resource(director)
+            .init_resource::<Targeting>()
+            .init_resource::<EnemyEvents>()
+            .add_enemy_event("salvo", 3.0, multiple_missiles)
+            .add_enemy_event("staggered_stream", 2.0, staggered_stream)
+            .add_enemy_event("pincer", 2.0, pincer)
+            .add_enemy_event("focused_strike", 1.0, focused_strike)
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game)
+                    .with_system(reset_spawner.system())
+                    .with_system(reset_difficulty.system())
+                    .with_system(reset_director.system()),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    // Everything that picks targets needs them refreshed first
+                    .with_system(update_targeting.system().label("targeting"))
+                    .with_system(update_difficulty.system())
+                    .with_system(run_director.system())
+                    .with_system(update_timer.system().after("targeting"))
+                    .with_system(run_flier_spawner.system())
+                    .with_system(spawn_fliers.system())
+                    .with_system(drop_missiles.system().after("targeting"))
+                    .with_system(check_offscreen_fliers.system())
+                    .with_system(spawn_boss.system())
+                    .with_system(move_boss.system())
+                    .with_system(fire_barrages.system().after("targeting"))
+                    .with_system(apply_damage.system())
+                    .with_system(check_boss_defeated.system())
+                    .with_system(update_health_bars.system().after("apply_velocity")),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::Game)
+                    .with_system(despawn_fliers.system())
+                    .with_system(despawn_boss.system()),
+            );
     }
 }
@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::Deserialize;
+
+use crate::{ground::GroundLevel, silo::Wrecked, Building, Silo};
+
+use super::{enemy_spawner::EnemyMissileSpawner, Waves};
+
+// How enemy missiles choose what to aim at, loaded from `waves.ron`
+#[derive(Debug, Clone, Deserialize)]
+pub struct TargetingConfig {
+    // How far (in pixels) an aimed missile can land from its target
+    pub miss_spread: f32,
+    // Chance of a missile being aimed at a structure on the first wave
+    pub base_accuracy: f32,
+    // Accuracy gained with each wave
+    pub accuracy_per_wave: f32,
+    pub max_accuracy: f32,
+}
+
+impl TargetingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        let values = [
+            ("miss_spread", self.miss_spread),
+            ("base_accuracy", self.base_accuracy),
+            ("accuracy_per_wave", self.accuracy_per_wave),
+            ("max_accuracy", self.max_accuracy),
+        ];
+        if let Some((name, _)) = values.iter().find(|(_, value)| !value.is_finite()) {
+            return Err(format!("targeting {} isn't a number", name));
+        }
+        if self.miss_spread < 0.0 {
+            return Err("targeting miss_spread can't be negative".to_string());
+        }
+        // Accuracy is used as a chance so has to stay between 0 and 1
+        if !(0.0..=1.0).contains(&self.max_accuracy) {
+            return Err("targeting max_accuracy must be between 0 and 1".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for TargetingConfig {
+    fn default() -> Self {
+        Self {
+            miss_spread: 48.0,
+            base_accuracy: 0.5,
+            accuracy_per_wave: 0.05,
+            max_accuracy: 0.9,
+        }
+    }
+}
+
+// Resource used to pick ground targets for enemy missiles. It is refreshed
+// every frame so destroyed structures are never targeted.
+//
+// `accuracy` is both the chance of a missile being aimed at a structure and
+// how tightly aimed missiles are grouped around it. Missiles that aren't aimed
+// land anywhere on the ground.
+#[derive(Debug, Default)]
+pub struct Targeting {
+    pub structures: Vec<Vec3>,
+    pub accuracy: f32,
+    pub miss_spread: f32,
+    pub half_width: f32,
+    // Copy of the GroundLevel so targets sit on the surface
+    ground: GroundLevel,
+}
+
+impl Targeting {
+    pub fn pick(&self, rng: &mut impl Rng) -> Vec3 {
+        match self.structures.choose(rng) {
+            Some(structure) if rng.gen_bool(self.accuracy as f64) => self.aim_at(structure.x, rng),
+            _ => self.anywhere(rng),
+        }
+    }
+
+    // Pick up to `count` targets, aimed missiles each go for a different
+    // structure where possible
+    pub fn pick_many(&self, count: usize, rng: &mut impl Rng) -> Vec<Vec3> {
+        let mut structures = self.structures.clone();
+        structures.shuffle(rng);
+
+        (0..count)
+            .map(|i| match structures.get(i % structures.len().max(1)) {
+                Some(structure) if rng.gen_bool(self.accuracy as f64) => {
+                    self.aim_at(structure.x, rng)
+                }
+                _ => self.anywhere(rng),
+            })
+            .collect()
+    }
+
+    fn aim_at(&self, x: f32, rng: &mut impl Rng) -> Vec3 {
+        let spread = self.miss_spread * (1.0 - self.accuracy);
+        let x = if spread > 0.0 {
+            x + rng.gen_range(-spread..spread)
+        } else {
+            x
+        };
+
+        self.ground_at(x)
+    }
+
+    fn anywhere(&self, rng: &mut impl Rng) -> Vec3 {
+        let x = rng.gen_range(-self.half_width..self.half_width);
+        self.ground_at(x)
+    }
+
+    // Point on the ground's surface at `x`
+    pub fn ground_at(&self, x: f32) -> Vec3 {
+        Vec3::new(x, self.ground.height_at(x), 0.0)
+    }
+}
+
+pub fn update_targeting(
+    windows: Res<Windows>,
+    waves: Res<Waves>,
+    spawner: Res<EnemyMissileSpawner>,
+    ground_level: Res<GroundLevel>,
+    mut targeting: ResMut<Targeting>,
+    structures: Query<&Transform, (Or<(With<Building>, With<Silo>)>, Without<Wrecked>)>,
+) {
+    let window = windows.get_primary().unwrap();
+    let config = &waves.targeting;
+    let wave = spawner.wave.max(1) - 1;
+
+    targeting.structures = structures.iter().map(|t| t.translation).collect();
+    targeting.accuracy = (config.base_accuracy + config.accuracy_per_wave * wave as f32)
+        .clamp(0.0, config.max_accuracy.min(1.0));
+    targeting.miss_spread = config.miss_spread;
+    targeting.half_width = window.width() / 2.0;
+    if ground_level.is_changed() {
+        targeting.ground = ground_level.clone();
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs::File;
+
+use ron::de::from_reader;
+use serde::Deserialize;
+
+use crate::{consts::ENEMY_MISSILE_VELOCITY, missile::MissileType};
+
+use super::targeting::TargetingConfig;
+
+// A group of identical missiles in a wave
+#[derive(Debug, Clone, Deserialize)]
+pub struct MissileGroup {
+    pub missile_type: MissileType,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Wave {
+    pub missiles: Vec<MissileGroup>,
+    // Speed of the enemy missiles in this wave
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+    // Seconds between each missile
+    pub interval: f32,
+    // Seconds between each enemy event, no events are triggered if `None`
+    #[serde(default)]
+    pub event_interval: Option<f32>,
+    // Send in the boss mothership at the start of the wave
+    #[serde(default)]
+    pub boss: bool,
+}
+
+impl Wave {
+    // Flatten the missile groups into the order they should be spawned in
+    pub fn missile_queue(&self) -> Vec<MissileType> {
+        self.missiles
+            .iter()
+            .flat_map(|group| std::iter::repeat(group.missile_type).take(group.count))
+            .collect()
+    }
+}
+
+fn default_speed() -> f32 {
+    ENEMY_MISSILE_VELOCITY
+}
+
+// Resource holding every wave loaded from `waves.ron`
+#[derive(Debug, Deserialize)]
+pub struct Waves {
+    pub waves: Vec<Wave>,
+    // Seconds between a wave being cleared and the next one starting
+    pub intermission: f32,
+    #[serde(default)]
+    pub targeting: TargetingConfig,
+}
+
+impl Waves {
+    pub fn load(path: &str) -> Self {
+        let f = File::open(path).expect("Could not open file");
+
+        let waves: Waves = match from_reader(f) {
+            Ok(x) => x,
+            Err(e) => {
+                println!("Failed to load waves: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        if waves.waves.is_empty() {
+            println!("Failed to load waves: {} contains no waves", path);
+            std::process::exit(1);
+        }
+        if let Err(e) = waves.targeting.validate() {
+            println!("Failed to load waves: {} {}", path, e);
+            std::process::exit(1);
+        }
+
+        waves
+    }
+
+    // Once the last wave has been cleared it keeps repeating
+    pub fn get(&self, wave: usize) -> &Wave {
+        &self.waves[wave.min(self.waves.len() - 1)]
+    }
+}
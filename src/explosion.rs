@@ -1,33 +1,173 @@
-use bevy::prelude::*;
+use std::fs::File;
+
+use bevy::{prelude::*, utils::HashMap};
+use ron::de::from_reader;
+use serde::Deserialize;
 
 use crate::{
     audio::PlayAudio,
     collision::CircleCollider,
     consts::EXPLOSION_SIZE,
     state::GameState,
-    team::{EnemyTeam, PlayerTeam, Team},
+    team::{CollisionLayers, Team},
     AssetHandles,
 };
 
 pub struct Explosion;
 
-struct Size(f32);
+// Position of a player explosion in a chain reaction
+pub struct Chain(pub u32);
+
+// How far through its lifecycle an explosion is. `size` scales the curve for
+// bigger explosions.
+struct Lifecycle {
+    kind: ExplosionKind,
+    size: f32,
+    elapsed: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum ExplosionKind {
+    Player,
+    Enemy,
+}
+
+impl ExplosionKind {
+    fn for_team(team: Team) -> Self {
+        match team {
+            Team::Player => ExplosionKind::Player,
+            Team::Enemy => ExplosionKind::Enemy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    SineInOut,
+    BackOut,
+}
+
+impl Easing {
+    // Map `t` from 0 - 1 onto the curve
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t).powi(2),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::SineInOut => -((std::f32::consts::PI * t).cos() - 1.0) / 2.0,
+            Easing::BackOut => {
+                // Overshoots slightly before settling
+                let c1 = 1.70158;
+                let c3 = c1 + 1.0;
+                1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Phase {
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+// An explosion grows to full size, holds there and then shrinks away
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExplosionCurve {
+    pub expand: Phase,
+    pub hold: f32,
+    pub contract: Phase,
+}
+
+impl ExplosionCurve {
+    pub fn duration(&self) -> f32 {
+        self.expand.duration + self.hold + self.contract.duration
+    }
+
+    // Scale of the explosion `elapsed` seconds after it started, from 0 - 1
+    pub fn scale_at(&self, elapsed: f32) -> f32 {
+        let contract_start = self.expand.duration + self.hold;
+
+        if elapsed < self.expand.duration {
+            self.expand.easing.apply(elapsed / self.expand.duration)
+        } else if elapsed < contract_start {
+            1.0
+        } else if self.contract.duration > 0.0 {
+            let t = (elapsed - contract_start) / self.contract.duration;
+            1.0 - self.contract.easing.apply(t)
+        } else {
+            0.0
+        }
+    }
+}
+
+// Resource holding the curve for each kind of explosion, loaded from
+// `explosions.ron`
+#[derive(Debug, Deserialize)]
+pub struct ExplosionCurves(pub HashMap<ExplosionKind, ExplosionCurve>);
+
+impl ExplosionCurves {
+    pub fn load(path: &str) -> Self {
+        let f = File::open(path).expect("Could not open file");
+
+        let curves: ExplosionCurves = match from_reader(f) {
+            Ok(x) => x,
+            Err(e) => {
+                println!("Failed to load explosions: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        for kind in [ExplosionKind::Player, ExplosionKind::Enemy].iter() {
+            if !curves.0.contains_key(kind) {
+                println!("Failed to load explosions: no curve for {:?}", kind);
+                std::process::exit(1);
+            }
+        }
+
+        curves
+    }
+}
 
 // Event to spawn explosion
 pub struct SpawnExplosion {
     pub position: Vec3,
     pub team: Team,
     pub size: f32,
+    // How many explosions led to this one, 0 for explosions that weren't set
+    // off by another explosion
+    pub chain: u32,
 }
 
 pub struct ExplosionPlugin;
 impl Plugin for ExplosionPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.add_event::<SpawnExplosion>().add_system_set(
-            SystemSet::on_update(GameState::Game)
-                .with_system(spawn_explosions.system())
-                .with_system(update_explosions.system()),
-        );
+        // Load the explosion curves from explosions.ron
+        let curves = ExplosionCurves::load("./config/explosions.ron");
+
+        app.add_event::<SpawnExplosion>()
+            .insert_resource(curves)
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(spawn_explosions.system())
+                    .with_system(update_explosions.system()),
+            );
     }
 }
 
@@ -43,42 +183,37 @@ fn spawn_explosions(
             Team::Enemy => asset_handles.explosion_red.clone(),
         };
 
-        if e.team == Team::Player {
-            commands
-                .spawn_bundle(SpriteBundle {
-                    material: explosion_material,
-                    transform: Transform {
-                        translation: e.position,
-                        ..Default::default()
-                    },
-                    visible: Visible {
-                        is_visible: true,
-                        is_transparent: true,
-                    },
+        let explosion = commands
+            .spawn_bundle(SpriteBundle {
+                material: explosion_material,
+                transform: Transform {
+                    translation: e.position,
+                    scale: Vec3::ZERO,
                     ..Default::default()
-                })
-                .insert(PlayerTeam)
-                .insert(Size(e.size))
-                .insert(CircleCollider(EXPLOSION_SIZE))
-                .insert(Explosion);
-        } else {
-            commands
-                .spawn_bundle(SpriteBundle {
-                    material: explosion_material,
-                    transform: Transform {
-                        translation: e.position,
-                        ..Default::default()
-                    },
-                    visible: Visible {
-                        is_visible: true,
-                        is_transparent: true,
-                    },
-                    ..Default::default()
-                })
-                .insert(EnemyTeam)
-                .insert(Size(1.0))
-                .insert(CircleCollider(EXPLOSION_SIZE))
-                .insert(Explosion);
+                },
+                visible: Visible {
+                    is_visible: true,
+                    is_transparent: true,
+                },
+                ..Default::default()
+            })
+            .insert(e.team)
+            .insert(CollisionLayers::explosion(e.team))
+            .insert(Lifecycle {
+                kind: ExplosionKind::for_team(e.team),
+                size: match e.team {
+                    Team::Player => e.size,
+                    Team::Enemy => 1.0,
+                },
+                elapsed: 0.0,
+            })
+            .insert(CircleCollider(0.0))
+            .insert(Explosion)
+            .id();
+
+        // Only player explosions score chains
+        if e.team == Team::Player {
+            commands.entity(explosion).insert(Chain(e.chain));
         }
 
         audio_events.send(PlayAudio {
@@ -90,23 +225,26 @@ fn spawn_explosions(
 fn update_explosions(
     mut commands: Commands,
     time: Res<Time>,
+    curves: Res<ExplosionCurves>,
     mut query: Query<(
         Entity,
         &Explosion,
-        &mut Size,
+        &mut Lifecycle,
         &mut Transform,
         &mut CircleCollider,
     )>,
 ) {
-    for (entity, _, mut size, mut transform, mut collider) in query.iter_mut() {
-        size.0 -= time.delta_seconds();
-        transform.scale.x = size.0;
-        transform.scale.y = size.0;
-        collider.0 = EXPLOSION_SIZE * size.0;
-
-        // Despawn explosion when it shrinks to zero size
-        // TODO - Add easing so the explosion grows quickly then smoothly shrinks?
-        if size.0 < 0.01 {
+    for (entity, _, mut lifecycle, mut transform, mut collider) in query.iter_mut() {
+        let curve = &curves.0[&lifecycle.kind];
+        lifecycle.elapsed += time.delta_seconds();
+
+        let scale = curve.scale_at(lifecycle.elapsed) * lifecycle.size;
+        transform.scale.x = scale;
+        transform.scale.y = scale;
+        collider.0 = EXPLOSION_SIZE * scale;
+
+        // Despawn explosion once it has finished shrinking
+        if lifecycle.elapsed >= curve.duration() {
             commands.entity(entity).despawn();
         }
     }
@@ -1,36 +1,149 @@
 use bevy::prelude::*;
 
-use crate::{state::GameState, ui::UpdateScoreUi};
+use crate::{
+    consts::{COMBO_TIME, MAX_COMBO_MULTIPLIER},
+    state::GameState,
+    ui::{UpdateComboUi, UpdateScoreUi},
+};
 
-// Event
+// Events
 pub struct UpdateScore(pub usize);
+pub struct RecordStat(pub Stat);
+// Sent when a chain reaction destroys a missile, holds the link's position in
+// the chain starting from 1
+pub struct ChainLink(pub u32);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stat {
+    // Enemy missile destroyed by the player
+    Intercepted,
+    // Enemy missile that reached the ground or a structure
+    Landed,
+    BuildingLost,
+    SiloLost,
+    // Player missile launched from a silo
+    MissileFired,
+}
+
+// Running totals for the current game
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GameStats {
+    pub intercepted: usize,
+    pub landed: usize,
+    pub buildings_lost: usize,
+    pub silos_lost: usize,
+    pub missiles_fired: usize,
+}
+
+impl GameStats {
+    fn record(&mut self, stat: Stat) {
+        match stat {
+            Stat::Intercepted => self.intercepted += 1,
+            Stat::Landed => self.landed += 1,
+            Stat::BuildingLost => self.buildings_lost += 1,
+            Stat::SiloLost => self.silos_lost += 1,
+            Stat::MissileFired => self.missiles_fired += 1,
+        }
+    }
+
+    // Stats recorded since `earlier` was copied
+    pub fn since(&self, earlier: &GameStats) -> GameStats {
+        GameStats {
+            intercepted: self.intercepted - earlier.intercepted,
+            landed: self.landed - earlier.landed,
+            buildings_lost: self.buildings_lost - earlier.buildings_lost,
+            silos_lost: self.silos_lost - earlier.silos_lost,
+            missiles_fired: self.missiles_fired - earlier.missiles_fired,
+        }
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct GameStatus {
     pub score: usize,
+    pub stats: GameStats,
+}
+
+// Resource tracking the current chain reaction. The combo ends once no new
+// links have been made for `COMBO_TIME` seconds.
+pub struct Combo {
+    pub chain: u32,
+    pub best: u32,
+    pub timer: Timer,
+}
+
+impl Combo {
+    // Score multiplier for a missile destroyed at this link of a chain
+    pub fn multiplier(link: u32) -> usize {
+        link.clamp(1, MAX_COMBO_MULTIPLIER) as usize
+    }
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Self {
+            chain: 0,
+            best: 0,
+            timer: Timer::from_seconds(COMBO_TIME, false),
+        }
+    }
 }
 
 pub struct GameStatusPlugin;
 impl Plugin for GameStatusPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_event::<UpdateScore>()
+            .add_event::<RecordStat>()
+            .add_event::<ChainLink>()
             .init_resource::<GameStatus>()
+            .init_resource::<Combo>()
             .add_system_set(SystemSet::on_enter(GameState::Game).with_system(reset.system()))
-            .add_system(update_game_status.system());
+            .add_system(update_game_status.system())
+            .add_system_set(
+                SystemSet::on_update(GameState::Game).with_system(update_combo.system()),
+            );
     }
 }
 
 fn update_game_status(
     mut game_status: ResMut<GameStatus>,
     mut score_events: EventReader<UpdateScore>,
+    mut stat_events: EventReader<RecordStat>,
     mut score_ui_events: EventWriter<UpdateScoreUi>,
 ) {
     for e in score_events.iter() {
         game_status.score += e.0;
         score_ui_events.send(UpdateScoreUi(game_status.score));
     }
+
+    for e in stat_events.iter() {
+        game_status.stats.record(e.0);
+    }
+}
+
+fn update_combo(
+    time: Res<Time>,
+    mut combo: ResMut<Combo>,
+    mut chain_events: EventReader<ChainLink>,
+    mut combo_ui_events: EventWriter<UpdateComboUi>,
+) {
+    for e in chain_events.iter() {
+        if e.0 > combo.chain {
+            combo.chain = e.0;
+            combo.best = combo.best.max(e.0);
+            combo_ui_events.send(UpdateComboUi(combo.chain));
+        }
+        combo.timer.reset();
+    }
+
+    if combo.timer.tick(time.delta()).just_finished() {
+        combo.chain = 0;
+        combo_ui_events.send(UpdateComboUi(0));
+    }
 }
 
-fn reset(mut game_status: ResMut<GameStatus>) {
+fn reset(mut game_status: ResMut<GameStatus>, mut combo: ResMut<Combo>) {
     game_status.score = 0;
+    game_status.stats = GameStats::default();
+    *combo = Combo::default();
 }
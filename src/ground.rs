@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+pub struct Ground;
+
+// Resource with the height of the ground's surface across the screen. It is
+// taken from the top opaque pixel in each column of the Ground sprite so any
+// hills drawn into the texture are followed too.
+#[derive(Debug, Default, Clone)]
+pub struct GroundLevel {
+    // Points along the surface sorted by x
+    surface: Vec<Vec2>,
+    // Height used when there's no ground at all, the bottom of the window
+    bottom: f32,
+}
+
+impl GroundLevel {
+    pub fn clear(&mut self, bottom: f32) {
+        self.surface.clear();
+        self.bottom = bottom;
+    }
+
+    // Add the surface of a Ground sprite drawn with `texture` at `transform`
+    pub fn add_ground(&mut self, transform: &Transform, texture: &Texture) {
+        let width = texture.size.width as usize;
+        let height = texture.size.height as usize;
+        let pixel_size = texture.format.pixel_size();
+        // Only 8 bit RGBA textures are checked for transparency, anything else
+        // is treated as flat
+        let has_alpha = pixel_size == 4 && texture.data.len() >= width * height * pixel_size;
+
+        for column in 0..width {
+            let top = if has_alpha {
+                match (0..height).find(|row| texture.data[(row * width + column) * 4 + 3] > 0) {
+                    Some(row) => row,
+                    None => continue,
+                }
+            } else {
+                0
+            };
+
+            let x = (column as f32 + 0.5 - width as f32 / 2.0) * transform.scale.x;
+            let y = (height as f32 / 2.0 - top as f32) * transform.scale.y;
+            self.surface
+                .push(transform.translation.truncate() + Vec2::new(x, y));
+        }
+
+        self.surface.sort_by(|a, b| a.x.total_cmp(&b.x));
+    }
+
+    // Height of the surface at `x`, between two columns it is interpolated and
+    // past either end the edge is carried on. With no ground at all it is the
+    // bottom of the window.
+    pub fn height_at(&self, x: f32) -> f32 {
+        let i = match self.surface.binary_search_by(|point| point.x.total_cmp(&x)) {
+            Ok(i) => return self.surface[i].y,
+            Err(i) => i,
+        };
+
+        match (self.surface.get(i.wrapping_sub(1)), self.surface.get(i)) {
+            (Some(left), Some(right)) => {
+                let t = (x - left.x) / (right.x - left.x);
+                left.y + (right.y - left.y) * t
+            }
+            (Some(edge), None) | (None, Some(edge)) => edge.y,
+            (None, None) => self.bottom,
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::fs::File;
+
+use bevy::prelude::*;
+use ron::de::from_reader;
+use serde::Deserialize;
+
+use crate::{
+    actions::ActionsMap,
+    consts::{LEVEL_EDGE_MARGIN, LEVEL_MIN_GAP},
+    silo::{fire_action, SMART_FIRE_ACTION},
+};
+
+// Where the silos and cities go. Positions are fractions of the window width
+// measured from the left edge, so a layout works at any window size.
+#[derive(Debug, Deserialize)]
+pub struct LevelLayout {
+    pub silos: Vec<f32>,
+    pub cities: Vec<f32>,
+}
+
+impl LevelLayout {
+    pub fn load(path: &str) -> Self {
+        let f = File::open(path).expect("Could not open file");
+
+        let mut layout: LevelLayout = match from_reader(f) {
+            Ok(x) => x,
+            Err(e) => {
+                println!("Failed to load level: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        if let Err(e) = layout.validate() {
+            println!("Failed to load level: {} {}", path, e);
+            std::process::exit(1);
+        }
+
+        // Silos are numbered from the left for the fire actions
+        layout.silos.sort_by(|a, b| a.total_cmp(b));
+
+        layout
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        // The game is over as soon as there are no cities left
+        if self.cities.is_empty() {
+            return Err("contains no cities".to_string());
+        }
+        if self.silos.is_empty() {
+            return Err("contains no silos".to_string());
+        }
+
+        let structures: Vec<(&str, f32)> = self
+            .silos
+            .iter()
+            .map(|x| ("silo", *x))
+            .chain(self.cities.iter().map(|x| ("city", *x)))
+            .collect();
+
+        // Anything closer to the edge would be partly off screen
+        let edges = LEVEL_EDGE_MARGIN..=1.0 - LEVEL_EDGE_MARGIN;
+        if let Some((name, x)) = structures.iter().find(|(_, x)| !edges.contains(x)) {
+            return Err(format!(
+                "has a {} at {} which is too close to the edge",
+                name, x
+            ));
+        }
+
+        for (i, (a_name, a)) in structures.iter().enumerate() {
+            for (b_name, b) in structures.iter().skip(i + 1) {
+                if (a - b).abs() < LEVEL_MIN_GAP {
+                    return Err(format!(
+                        "has a {} at {} on top of a {} at {}",
+                        a_name, a, b_name, b
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // `--level <path>` loads a custom layout instead of level.ron
+    fn path_from_args() -> String {
+        let args: Vec<String> = std::env::args().collect();
+
+        match args.iter().position(|arg| arg == "--level") {
+            Some(i) => match args.get(i + 1) {
+                Some(path) => path.clone(),
+                None => {
+                    println!("--level must be followed by a path");
+                    std::process::exit(1);
+                }
+            },
+            None => "./config/level.ron".to_string(),
+        }
+    }
+}
+
+pub struct LevelPlugin;
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        // Load the silo and city layout from level.ron
+        let layout = LevelLayout::load(&LevelLayout::path_from_args());
+
+        app.insert_resource(layout)
+            .add_startup_system(check_fire_bindings.system());
+    }
+}
+
+// Bindings are shared between levels so they can fire silos this level doesn't
+// have, or leave some of its silos with no way to fire them
+fn check_fire_bindings(level: Res<LevelLayout>, actions_map: Res<ActionsMap>) {
+    let silo_actions: Vec<String> = (0..level.silos.len()).map(fire_action).collect();
+
+    for action in actions_map.keyboard.values() {
+        if action != SMART_FIRE_ACTION && !silo_actions.contains(action) {
+            warn!(
+                "\"{}\" is bound but isn't used by any silo in this level",
+                action
+            );
+        }
+    }
+
+    for action in silo_actions.iter() {
+        if !actions_map.keyboard.values().any(|bound| bound == action) {
+            warn!(
+                "\"{}\" has no key bound so that silo can't be fired",
+                action
+            );
+        }
+    }
+}
@@ -18,9 +18,12 @@ const FRAGMENT_SHADER: &str = include_str!("shader.frag");
 const MAX_LINE_LENGTH: f32 = 100.0;
 const LINE_GROWTH_SPEED: f32 = 80.0;
 const LINE_WIDTH: f32 = 3.0;
+// How far (in radians) a missile can turn before a new trail segment is started
+const LINE_SEGMENT_ANGLE: f32 = 0.1;
 
 pub struct LineTrail {
-    owner: Entity, // The missile that spawned the LineTrail
+    owner: Entity,   // The missile that spawned the LineTrail
+    direction: Vec2, // Direction the owner was travelling when the LineTrail spawned
 }
 
 #[derive(RenderResources, Default, TypeUuid)]
@@ -49,6 +52,7 @@ impl Plugin for LineTrailPlugin {
                     .with_system(spawn_line_trails.system())
                     .with_system(update_line_scale.system())
                     .with_system(update_line_velocity.system())
+                    .with_system(follow_owners.system().after("apply_velocity"))
                     .with_system(despawn_line_trails.system()),
             );
     }
@@ -123,7 +127,10 @@ fn spawn_line_trails(
                 )]),
                 ..Default::default()
             })
-            .insert(LineTrail { owner: e.owner })
+            .insert(LineTrail {
+                owner: e.owner,
+                direction: e.velocity.normalize_or_zero(),
+            })
             .insert(mat)
             .insert(Velocity(e.velocity));
     }
@@ -157,3 +164,39 @@ fn update_line_velocity(owner_query: Query<Entity>, mut query: Query<(&LineTrail
         }
     }
 }
+
+// Keep line trails attached to missiles that change direction. A LineTrail is
+// a straight line, so once its owner turns too far a new segment is started
+// and the old one is left behind to shrink away.
+fn follow_owners(
+    owner_query: Query<(&Transform, &Velocity, &Team), Without<LineTrail>>,
+    mut query: Query<(&LineTrail, &mut Velocity, &mut Transform)>,
+    mut events: EventWriter<SpawnLineTrail>,
+) {
+    for (line_trail, mut velocity, mut transform) in query.iter_mut() {
+        if velocity.0 == Vec2::ZERO {
+            continue;
+        }
+
+        if let Ok((owner_transform, owner_velocity, team)) = owner_query.get(line_trail.owner) {
+            if owner_velocity.0 == Vec2::ZERO {
+                continue;
+            }
+
+            transform.translation = owner_transform.translation;
+
+            if line_trail.direction.angle_between(owner_velocity.0).abs() > LINE_SEGMENT_ANGLE {
+                velocity.0 = Vec2::ZERO;
+                events.send(SpawnLineTrail {
+                    position: owner_transform.translation,
+                    velocity: owner_velocity.0,
+                    rotation: Vec2::new(0.0, 1.0).angle_between(owner_velocity.0),
+                    owner: line_trail.owner,
+                    team: *team,
+                });
+            } else {
+                velocity.0 = owner_velocity.0;
+            }
+        }
+    }
+}
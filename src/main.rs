@@ -4,43 +4,60 @@ use rand::prelude::*;
 
 mod actions;
 mod audio;
+mod ballistics;
 mod collision;
 mod consts;
 mod debris;
 mod enemy;
 mod explosion;
 mod game_status;
+mod ground;
+mod level;
 mod line_trail;
 mod missile;
 mod player_status;
 mod powerups;
+mod rng;
 mod silo;
 mod state;
+mod stress;
 mod team;
 mod ui;
 
 use actions::{Actions, ActionsPlugin};
-use audio::AudioPlugin;
-use collision::CollisionPlugin;
-use consts::{SILO_MAX_MISSILES, SILO_RELOAD_TIME};
+use audio::{AudioPlugin, PlayAudio};
+use ballistics::{Ballistics, BallisticsPlugin};
+use collision::{AabbCollider, CollisionPlugin};
+use consts::{SILO_MAX_MISSILES, SILO_RELOAD_TIME, SMART_FIRE_THREAT_RADIUS};
 use debris::{DebrisPlugin, DebrisType};
 use enemy::EnemySpawnerPlugin;
 use explosion::{Explosion, ExplosionPlugin};
-use game_status::GameStatusPlugin;
+use game_status::{GameStatusPlugin, RecordStat, Stat};
+use ground::{Ground, GroundLevel};
+use level::{LevelLayout, LevelPlugin};
 use line_trail::{LineMaterial, LineTrail, LineTrailPlugin};
-use missile::{Missile, MissilePlugin, SpawnMissile};
-use player_status::PlayerStatusPlugin;
+use missile::{Missile, MissilePlugin, MissileType, SpawnMissile, Target, TargetMarker};
+use player_status::{PlayerStatus, PlayerStatusPlugin};
 use powerups::PowerupsPlugin;
+use rng::{GameRng, GameRngPlugin};
 use silo::{
-    Silo, SiloLocation, SiloMissileCountUi, SiloMissileCountUpdate, SiloPlugin, SiloReloadUi,
+    fire_action, fire_cost, Silo, SiloMissileCountUi, SiloMissileCountUpdate, SiloPlugin,
+    SiloReloadUi, Wrecked, SMART_FIRE_ACTION,
 };
 use state::GameState;
-use team::Team;
-use ui::{GameOverPlugin, MainMenuPlugin, OptionsMenuPlugin, PauseMenuPlugin, ScoreUiPlugin};
+use stress::StressPlugin;
+use team::{CollisionLayers, Team};
+use ui::{
+    ComboUiPlugin, GameOverPlugin, MainMenuPlugin, OptionsMenuPlugin, PauseMenuPlugin,
+    ScoreUiPlugin, WaveUiPlugin,
+};
 
 struct Building;
 
-struct Ground;
+// Event to spawn a building on the ground, used to rebuild destroyed cities
+pub struct SpawnBuilding {
+    pub x_position: f32,
+}
 
 struct Velocity(Vec2);
 
@@ -64,6 +81,11 @@ pub struct AssetHandles {
     // Game
     pub missile_red: Handle<ColorMaterial>,
     pub missile_green: Handle<ColorMaterial>,
+    pub smart_bomb: Handle<ColorMaterial>,
+    pub bomber: Handle<ColorMaterial>,
+    pub satellite: Handle<ColorMaterial>,
+    pub boss: Handle<ColorMaterial>,
+    pub health_bar: Handle<ColorMaterial>,
     pub explosion_red: Handle<ColorMaterial>,
     pub explosion_green: Handle<ColorMaterial>,
     pub building_01: Handle<ColorMaterial>,
@@ -75,11 +97,13 @@ pub struct AssetHandles {
     pub silo_debris_01: Handle<ColorMaterial>,
     pub silo_reload_loading: Handle<ColorMaterial>,
     pub silo_reload_ready: Handle<ColorMaterial>,
+    pub crosshairs: Vec<Handle<ColorMaterial>>, // Tinted for each silo
 
     // Powerups
     pub score_powerup: Handle<ColorMaterial>,
     pub explosion_size_powerup: Handle<ColorMaterial>,
     pub missile_speed_powerup: Handle<ColorMaterial>,
+    pub homing_powerup: Handle<ColorMaterial>,
 
     // Line Trail
     pub line_trail: Handle<Mesh>,
@@ -112,6 +136,11 @@ fn setup(
     let building_03_tex: Handle<Texture> = asset_server.load("building_03.png");
     let missile_red_tex: Handle<Texture> = asset_server.load("missile_red.png");
     let missile_green_tex: Handle<Texture> = asset_server.load("missile_green.png");
+    let smart_bomb_tex: Handle<Texture> = asset_server.load("smart_bomb.png");
+    let bomber_tex: Handle<Texture> = asset_server.load("bomber.png");
+    let satellite_tex: Handle<Texture> = asset_server.load("satellite.png");
+    let boss_tex: Handle<Texture> = asset_server.load("boss.png");
+    let crosshair_tex: Handle<Texture> = asset_server.load("crosshair.png");
     let explosion_red_tex: Handle<Texture> = asset_server.load("explosion_red.png");
     let explosion_green_tex: Handle<Texture> = asset_server.load("explosion_green.png");
     let debris_01: Handle<Texture> = asset_server.load("debris_01.png");
@@ -119,6 +148,7 @@ fn setup(
     let score_powerup_tex = asset_server.load("score_powerup.png");
     let explosion_size_powerup_tex = asset_server.load("explosion_size_powerup.png");
     let missile_speed_powerup_tex = asset_server.load("missile_speed_powerup.png");
+    let homing_powerup_tex = asset_server.load("homing_powerup.png");
 
     asset_handles.font = asset_server.load("BlocTekRegular-gxEZ4.ttf");
     asset_handles.simple_font = asset_server.load("MontserratBold-DOWZd.ttf");
@@ -127,6 +157,14 @@ fn setup(
     asset_handles.button_click = materials.add(Color::rgb(0.35, 0.85, 0.35).into());
     asset_handles.missile_red = materials.add(missile_red_tex.into());
     asset_handles.missile_green = materials.add(missile_green_tex.into());
+    asset_handles.smart_bomb = materials.add(smart_bomb_tex.into());
+    asset_handles.bomber = materials.add(bomber_tex.into());
+    asset_handles.satellite = materials.add(satellite_tex.into());
+    asset_handles.boss = materials.add(boss_tex.into());
+    asset_handles.health_bar = materials.add(ColorMaterial {
+        color: Color::rgb(0.97, 0.38, 0.38),
+        texture: None,
+    });
     asset_handles.explosion_red = materials.add(explosion_red_tex.into());
     asset_handles.explosion_green = materials.add(explosion_green_tex.into());
     asset_handles.building_01 = materials.add(building_01_tex.into());
@@ -144,9 +182,24 @@ fn setup(
         color: Color::rgb(0.3, 0.9, 0.3),
         texture: None,
     });
+    asset_handles.crosshairs = [
+        Color::rgb(0.49, 0.67, 0.87),
+        Color::rgb(0.87, 0.81, 0.49),
+        Color::rgb(0.87, 0.49, 0.75),
+        Color::rgb(0.49, 0.87, 0.78),
+    ]
+    .iter()
+    .map(|color| {
+        materials.add(ColorMaterial {
+            color: *color,
+            texture: Some(crosshair_tex.clone()),
+        })
+    })
+    .collect();
     asset_handles.score_powerup = materials.add(score_powerup_tex.into());
     asset_handles.explosion_size_powerup = materials.add(explosion_size_powerup_tex.into());
     asset_handles.missile_speed_powerup = materials.add(missile_speed_powerup_tex.into());
+    asset_handles.homing_powerup = materials.add(homing_powerup_tex.into());
     asset_handles.rebind_widget = materials.add(Color::rgba(0.0, 0.0, 0.0, 0.9).into());
     asset_handles.none = materials.add(Color::NONE.into());
     asset_handles.button_hover_audio = asset_server.load("audio/Cursor Rollover 1.wav");
@@ -166,192 +219,334 @@ fn setup_game(
     windows: Res<Windows>,
     color_mats: Res<Assets<ColorMaterial>>,
     textures: Res<Assets<Texture>>,
+    level: Res<LevelLayout>,
+    mut ground_level: ResMut<GroundLevel>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     let (window_width, window_half_width, window_half_height) = {
         let window = windows.get_primary().unwrap();
         (window.width(), window.width() / 2.0, window.height() / 2.0)
     };
-    let (ground_y, ground_height) = {
-        // This can fail if the texture hasn't loaded but it shouldn't happen
-        // as long as the app isn't run immediately with GameState set.
-        let mat = color_mats.get(asset_handles.ground.clone()).unwrap();
-        let tex_handle = mat.texture.clone().unwrap();
-        let tex_height = textures.get(tex_handle).unwrap().size.height as f32;
-        (-window_half_height + tex_height / 2.0, tex_height)
-    };
-    let silo_height = {
-        let mat = color_mats.get(asset_handles.silo.clone()).unwrap();
-        let tex_handle = mat.texture.clone().unwrap();
-        let tex_height = textures.get(tex_handle).unwrap().size.height as f32;
-        tex_height
-    };
-    let building_height = {
-        // All buildings are currently the same height...
-        let mat = color_mats.get(asset_handles.building_01.clone()).unwrap();
-        let tex_handle = mat.texture.clone().unwrap();
-        let tex_height = textures.get(tex_handle).unwrap().size.height as f32;
-        tex_height
+    let silo_height = texture_height(&asset_handles.silo, &color_mats, &textures);
+    let silo_collider = texture_collider(&asset_handles.silo, &color_mats, &textures);
+
+    // Ground, stretched so it always covers the width of the window
+    let ground_texture = material_texture(&asset_handles.ground, &color_mats, &textures);
+    let ground_transform = Transform {
+        translation: Vec3::new(
+            0.0,
+            -window_half_height + ground_texture.size.height as f32 / 2.0,
+            0.0,
+        ),
+        scale: Vec3::new(
+            (window_width / ground_texture.size.width as f32).max(2.0),
+            1.0,
+            1.0,
+        ),
+        ..Default::default()
     };
 
-    // Ground
+    ground_level.clear(-window_half_height);
+    ground_level.add_ground(&ground_transform, ground_texture);
+
     commands
         .spawn_bundle(SpriteBundle {
             material: asset_handles.ground.clone(),
-            transform: Transform {
-                translation: Vec3::new(0.0, ground_y, 0.0),
-                scale: Vec3::new(2.0, 1.0, 1.0),
-                ..Default::default()
-            },
+            transform: ground_transform,
             ..Default::default()
         })
         .insert(Ground);
 
-    // Silos and Buildings
-    let step_size = window_width / 9.0; // 3 silos + 6 buildings
-    let half_step = step_size / 2.0;
-
-    for i in 0..9 {
-        match i {
-            0 | 4 | 8 => {
-                let x = (step_size * i as f32) + half_step - window_half_width;
-                let y = silo_height / 2.0 + ground_height - window_half_height;
-
-                let silo_location = match i {
-                    0 => SiloLocation::Left,
-                    4 => SiloLocation::Middle,
-                    8 => SiloLocation::Right,
-                    _ => panic!("How the hell did this happen!?"),
-                };
-
-                let silo = Silo {
-                    location: silo_location,
-                    missiles: SILO_MAX_MISSILES - 1,
-                };
-
-                commands
-                    .spawn_bundle(SpriteBundle {
-                        material: asset_handles.silo.clone(),
-                        transform: Transform {
-                            translation: Vec3::new(x, y, 0.0),
-                            ..Default::default()
-                        },
-                        ..Default::default()
-                    })
-                    .insert(silo)
-                    .insert(Timer::new(Duration::from_secs_f32(SILO_RELOAD_TIME), false));
-
-                // Reload Ui
-                commands
-                    .spawn_bundle(SpriteBundle {
-                        sprite: Sprite {
-                            size: Vec2::new(50.0, 10.0),
-                            ..Default::default()
-                        },
-                        material: asset_handles.silo_reload_loading.clone(),
+    // Silos
+    for (index, fraction) in level.silos.iter().enumerate() {
+        let x = window_width * fraction - window_half_width;
+        let y = ground_level.height_at(x) + silo_height / 2.0;
+
+        let silo = Silo {
+            index,
+            missiles: SILO_MAX_MISSILES - 1,
+            marker: asset_handles.crosshairs[index % asset_handles.crosshairs.len()].clone(),
+        };
+
+        commands
+            .spawn_bundle(SpriteBundle {
+                material: asset_handles.silo.clone(),
+                transform: Transform {
+                    translation: Vec3::new(x, y, 0.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .insert(silo)
+            .insert(silo_collider)
+            .insert(CollisionLayers::new(
+                CollisionLayers::STRUCTURE,
+                CollisionLayers::NONE,
+            ))
+            .insert(Timer::new(Duration::from_secs_f32(SILO_RELOAD_TIME), false));
+
+        // Reload Ui
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    size: Vec2::new(50.0, 10.0),
+                    ..Default::default()
+                },
+                material: asset_handles.silo_reload_loading.clone(),
+                transform: Transform {
+                    translation: Vec3::new(x, y - 50.0, 0.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .insert(SiloReloadUi { silo: index });
+
+        // Missile Count Ui
+        commands
+            .spawn()
+            .insert(GlobalTransform {
+                translation: Vec3::new(x, y - 30.0, 0.0),
+                ..Default::default()
+            })
+            .insert(Transform {
+                translation: Vec3::new(x, y - 30.0, 0.0),
+                ..Default::default()
+            })
+            .insert(SiloMissileCountUi { silo: index })
+            .with_children(|parent| {
+                let space = 25.0;
+                for i in 0..3 {
+                    parent.spawn_bundle(SpriteBundle {
+                        material: asset_handles.missile_green.clone(),
                         transform: Transform {
-                            translation: Vec3::new(x, y - 50.0, 0.0),
+                            translation: Vec3::new(-space + (space * i as f32), 0.0, 0.0),
                             ..Default::default()
                         },
                         ..Default::default()
-                    })
-                    .insert(SiloReloadUi)
-                    .insert(silo_location);
-
-                // Missile Count Ui
-                commands
-                    .spawn()
-                    .insert(GlobalTransform {
-                        translation: Vec3::new(x, y - 30.0, 0.0),
-                        ..Default::default()
-                    })
-                    .insert(Transform {
-                        translation: Vec3::new(x, y - 30.0, 0.0),
-                        ..Default::default()
-                    })
-                    .insert(SiloMissileCountUi)
-                    .insert(silo_location)
-                    .with_children(|parent| {
-                        let space = 25.0;
-                        for i in 0..3 {
-                            parent.spawn_bundle(SpriteBundle {
-                                material: asset_handles.missile_green.clone(),
-                                transform: Transform {
-                                    translation: Vec3::new(-space + (space * i as f32), 0.0, 0.0),
-                                    ..Default::default()
-                                },
-                                ..Default::default()
-                            });
-                        }
                     });
-            }
-            _ => {
-                let mut rng = thread_rng();
-                let rand: usize = rng.gen_range(0..3);
-                let building_material = match rand {
-                    0 => asset_handles.building_01.clone(),
-                    1 => asset_handles.building_02.clone(),
-                    2 => asset_handles.building_03.clone(),
-                    // Maybe a panic isn't really needed here...
-                    _ => panic!("Error choosing building material."),
-                };
+                }
+            });
+    }
 
-                let x = (step_size * i as f32) + half_step - window_half_width;
-                let y = building_height / 2.0 + ground_height - window_half_height;
+    // Buildings
+    for fraction in level.cities.iter() {
+        let x = window_width * fraction - window_half_width;
+        spawn_building(
+            &mut commands,
+            &asset_handles,
+            &ground_level,
+            &color_mats,
+            &textures,
+            game_rng.stream("setup_game"),
+            x,
+        );
+    }
+}
 
-                commands
-                    .spawn_bundle(SpriteBundle {
-                        material: building_material,
-                        transform: Transform {
-                            translation: Vec3::new(x, y, 0.0),
-                            ..Default::default()
-                        },
-                        ..Default::default()
-                    })
-                    .insert(Building);
-            }
-        }
+// Height of the texture used by a material. This can fail if the texture
+// hasn't loaded but it shouldn't happen as long as the app isn't run
+// immediately with GameState set.
+fn texture_height(
+    material: &Handle<ColorMaterial>,
+    color_mats: &Assets<ColorMaterial>,
+    textures: &Assets<Texture>,
+) -> f32 {
+    material_texture(material, color_mats, textures).size.height as f32
+}
+
+// Box collider fitted to the texture used by a material, same caveats as
+// `texture_height`
+fn texture_collider(
+    material: &Handle<ColorMaterial>,
+    color_mats: &Assets<ColorMaterial>,
+    textures: &Assets<Texture>,
+) -> AabbCollider {
+    AabbCollider::from_texture(material_texture(material, color_mats, textures))
+}
+
+fn material_texture<'a>(
+    material: &Handle<ColorMaterial>,
+    color_mats: &Assets<ColorMaterial>,
+    textures: &'a Assets<Texture>,
+) -> &'a Texture {
+    let mat = color_mats.get(material.clone()).unwrap();
+    let tex_handle = mat.texture.clone().unwrap();
+    textures.get(tex_handle).unwrap()
+}
+
+// Spawn a building with a random texture standing on the ground
+fn spawn_building(
+    commands: &mut Commands,
+    asset_handles: &AssetHandles,
+    ground_level: &GroundLevel,
+    color_mats: &Assets<ColorMaterial>,
+    textures: &Assets<Texture>,
+    rng: &mut impl Rng,
+    x: f32,
+) {
+    // All buildings are currently the same height...
+    let building_height = texture_height(&asset_handles.building_01, color_mats, textures);
+
+    let building_material = match rng.gen_range(0..3) {
+        0 => asset_handles.building_01.clone(),
+        1 => asset_handles.building_02.clone(),
+        2 => asset_handles.building_03.clone(),
+        // Maybe a panic isn't really needed here...
+        _ => panic!("Error choosing building material."),
+    };
+
+    let y = ground_level.height_at(x) + building_height / 2.0;
+    let collider = texture_collider(&building_material, color_mats, textures);
+
+    commands
+        .spawn_bundle(SpriteBundle {
+            material: building_material,
+            transform: Transform {
+                translation: Vec3::new(x, y, 0.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(Building)
+        .insert(collider)
+        .insert(CollisionLayers::new(
+            CollisionLayers::STRUCTURE,
+            CollisionLayers::NONE,
+        ));
+}
+
+fn spawn_buildings(
+    mut commands: Commands,
+    asset_handles: Res<AssetHandles>,
+    ground_level: Res<GroundLevel>,
+    color_mats: Res<Assets<ColorMaterial>>,
+    textures: Res<Assets<Texture>>,
+    mut game_rng: ResMut<GameRng>,
+    mut events: EventReader<SpawnBuilding>,
+) {
+    for e in events.iter() {
+        spawn_building(
+            &mut commands,
+            &asset_handles,
+            &ground_level,
+            &color_mats,
+            &textures,
+            game_rng.stream("spawn_buildings"),
+            e.x_position,
+        );
     }
 }
 
 fn shoot(
     // keys: Res<Input<KeyCode>>,
     actions: Res<Actions>,
+    asset_handles: Res<AssetHandles>,
     mouse_pos: Res<MousePosition>,
-    mut query: Query<(&mut Silo, &mut Timer, &Transform)>,
+    player_status: Res<PlayerStatus>,
+    ballistics: Res<Ballistics>,
+    mut query: Query<(&mut Silo, &mut Timer, &Transform, Option<&Wrecked>)>,
+    enemy_missiles: Query<(&Team, &Target), With<Missile>>,
     mut events: EventWriter<SpawnMissile>,
     mut silo_ui_events: EventWriter<SiloMissileCountUpdate>,
+    mut stat_events: EventWriter<RecordStat>,
+    mut audio_events: EventWriter<PlayAudio>,
 ) {
     let target = Vec3::new(mouse_pos.position.x, mouse_pos.position.y, 0.0);
     let team = Team::Player;
 
-    for (mut silo, mut timer, transform) in query.iter_mut() {
-        if silo.missiles > 0 {
-            if silo.location == SiloLocation::Left && actions.just_pressed("Fire Left Silo")
-                || silo.location == SiloLocation::Middle && actions.just_pressed("Fire Middle Silo")
-                || silo.location == SiloLocation::Right && actions.just_pressed("Fire Right Silo")
-            {
-                silo.missiles -= 1;
-                if timer.finished() {
-                    timer.reset();
-                }
-                events.send(SpawnMissile {
-                    position: transform.translation,
-                    target,
-                    team,
-                });
-                silo_ui_events.send(SiloMissileCountUpdate {
-                    location: silo.location,
-                    count: silo.missiles,
-                });
+    // Silos picked by their own fire action, trying to fire a wrecked or
+    // empty silo just clicks
+    let mut firing = vec![];
+    let mut dry_fire = false;
+    for (silo, _, _, wrecked) in query.iter_mut() {
+        if actions.just_pressed(&fire_action(silo.index)) {
+            if wrecked.is_none() && silo.missiles > 0 {
+                firing.push(silo.index);
+            } else {
+                dry_fire = true;
+            }
+        }
+    }
+
+    // Smart fire takes the best loaded silo that isn't already firing
+    if actions.just_pressed(SMART_FIRE_ACTION) {
+        let best = query
+            .iter_mut()
+            .filter(|(silo, _, _, wrecked)| {
+                wrecked.is_none() && silo.missiles > 0 && !firing.contains(&silo.index)
+            })
+            .map(|(silo, _, transform, _)| {
+                let position = transform.translation.truncate();
+                let threats = enemy_missiles
+                    .iter()
+                    .filter(|(team, missile_target)| {
+                        **team == Team::Enemy
+                            && (missile_target.0.x - position.x).abs() < SMART_FIRE_THREAT_RADIUS
+                    })
+                    .count();
+                // Ballistic missiles arc towards the target, the same as in
+                // `spawn_missiles`
+                let speed = player_status.missile_speed;
+                let flight_time = if ballistics.config.enabled && !player_status.homing {
+                    ballistics
+                        .launch(team, position, mouse_pos.position, speed)
+                        .1
+                } else {
+                    position.distance(mouse_pos.position) / speed
+                };
+                (silo.index, fire_cost(&silo, flight_time, threats))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        match best {
+            Some((index, _)) => firing.push(index),
+            // Every silo is empty or wrecked
+            None => dry_fire = true,
+        }
+    }
+
+    // Reuses the rebind widget's close sound as the click
+    if dry_fire {
+        audio_events.send(PlayAudio {
+            handle: asset_handles.rebind_widget_close_audio.clone(),
+        });
+    }
+
+    for (mut silo, mut timer, transform, _) in query.iter_mut() {
+        if firing.contains(&silo.index) {
+            silo.missiles -= 1;
+            if timer.finished() {
+                timer.reset();
             }
+            events.send(SpawnMissile {
+                position: transform.translation,
+                target,
+                team,
+                missile_type: MissileType::Standard,
+                speed: player_status.missile_speed,
+                marker: Some(silo.marker.clone()),
+            });
+            silo_ui_events.send(SiloMissileCountUpdate {
+                silo: silo.index,
+                count: silo.missiles,
+            });
+            stat_events.send(RecordStat(Stat::MissileFired));
         }
     }
 }
 
-fn get_mouse_pos(mut cursor_evt: EventReader<CursorMoved>, mut mouse_pos: ResMut<MousePosition>) {
+fn get_mouse_pos(
+    windows: Res<Windows>,
+    mut cursor_evt: EventReader<CursorMoved>,
+    mut mouse_pos: ResMut<MousePosition>,
+) {
     for event in cursor_evt.iter() {
-        let x = event.position.x - 1280.0 / 2.0;
-        let y = event.position.y - 720.0 / 2.0;
+        let window = match windows.get(event.id) {
+            Some(window) => window,
+            None => continue,
+        };
+        let x = event.position.x - window.width() / 2.0;
+        let y = event.position.y - window.height() / 2.0;
         mouse_pos.position = Vec2::new(x, y);
         // println!("{:?}", event);
         // println!("x: {}, y: {}", x, y);
@@ -380,6 +575,7 @@ fn despawn_game(
             With<Ground>,
             With<SiloReloadUi>,
             With<SiloMissileCountUi>,
+            With<TargetMarker>,
         )>,
     >,
 ) {
@@ -422,6 +618,8 @@ fn main() {
         .add_plugin(CollisionPlugin)
         .add_plugin(LineTrailPlugin)
         .add_plugin(ScoreUiPlugin)
+        .add_plugin(WaveUiPlugin)
+        .add_plugin(ComboUiPlugin)
         .add_plugin(DebrisPlugin)
         .add_plugin(GameOverPlugin)
         .add_plugin(SiloPlugin)
@@ -433,15 +631,22 @@ fn main() {
         .add_plugin(PauseMenuPlugin)
         .add_plugin(PlayerStatusPlugin)
         .add_plugin(GameStatusPlugin)
+        .add_plugin(GameRngPlugin)
+        .add_plugin(BallisticsPlugin)
+        .add_plugin(LevelPlugin)
+        .add_plugin(StressPlugin)
+        .add_event::<SpawnBuilding>()
         .init_resource::<MousePosition>()
         .init_resource::<AssetHandles>()
+        .init_resource::<GroundLevel>()
         .add_startup_system(setup.system().label("setup"))
         .add_system(get_mouse_pos.system().label("get_mouse_position"))
         .add_system_set(SystemSet::on_enter(GameState::Game).with_system(setup_game.system()))
         .add_system_set(
             SystemSet::on_update(GameState::Game)
                 .with_system(shoot.system().after("get_mouse_position"))
-                .with_system(apply_velocity.system())
+                .with_system(apply_velocity.system().label("apply_velocity"))
+                .with_system(spawn_buildings.system())
                 .with_system(check_game_over.system())
                 .with_system(check_pause.system()),
         )
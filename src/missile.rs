@@ -1,26 +1,79 @@
 use bevy::prelude::*;
+use rand::prelude::*;
+use serde::Deserialize;
 
 use crate::{
-    collision::CircleCollider,
-    consts::{ENEMY_MISSILE_VELOCITY, MISSILE_RADIUS},
-    explosion::SpawnExplosion,
+    ballistics::{Ballistic, Ballistics, Fuse},
+    collision::{
+        segment_hits_circle, start_position, CircleCollider, MissileDetonated, PreviousPosition,
+    },
+    consts::{
+        HOMING_CONE, HOMING_RANGE, HOMING_TURN_RATE, MIRV_MAX_SPLIT_HEIGHT, MIRV_MIN_SPLIT_HEIGHT,
+        MIRV_SCALE, MIRV_WARHEADS, MISSILE_RADIUS, MISSILE_VALUE, SMART_BOMB_AVOID_DISTANCE,
+        SMART_BOMB_TURN_RATE, SMART_BOMB_VALUE, TARGET_REACHED_RADIUS,
+    },
+    enemy::{Difficulty, Targeting},
+    explosion::Explosion,
+    ground::GroundLevel,
     line_trail::SpawnLineTrail,
     player_status::PlayerStatus,
+    rng::GameRng,
     state::GameState,
-    team::{EnemyTeam, PlayerTeam, Team},
+    team::{CollisionLayers, Team},
     AssetHandles, Velocity,
 };
 
 pub struct Missile;
 
 // Position the missile should explode at if it doesn't hit anything
-struct Target(Vec3);
+pub struct Target(pub Vec3);
+
+// Missile that splits into several warheads once it falls below `split_height`
+// (a fraction of the window height) above the ground, `speed` is the speed it
+// was launched with before difficulty was applied
+struct Mirv {
+    split_height: f32,
+    speed: f32,
+}
+
+// Missile that steers around player explosions on its way to the target
+struct SmartBomb;
+
+// Player missile fired while the homing powerup is active, it chases the
+// nearest enemy missile in front of it
+struct Homing;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum MissileType {
+    Standard,
+    Mirv,
+    SmartBomb,
+}
+
+impl MissileType {
+    // Score for destroying this missile with an explosion
+    pub fn value(&self) -> usize {
+        match self {
+            MissileType::Standard | MissileType::Mirv => MISSILE_VALUE,
+            MissileType::SmartBomb => SMART_BOMB_VALUE,
+        }
+    }
+}
 
 // Spawn missile event
 pub struct SpawnMissile {
     pub position: Vec3,
     pub target: Vec3,
     pub team: Team,
+    pub missile_type: MissileType,
+    pub speed: f32,
+    // Material for a marker shown at the target until the missile is gone
+    pub marker: Option<Handle<ColorMaterial>>,
+}
+
+// Marker showing where a missile will explode
+pub struct TargetMarker {
+    owner: Entity,
 }
 
 pub struct MissilePlugin;
@@ -29,7 +82,16 @@ impl Plugin for MissilePlugin {
         app.add_event::<SpawnMissile>().add_system_set(
             SystemSet::on_update(GameState::Game)
                 .with_system(spawn_missiles.system())
-                .with_system(check_target_reached.system()),
+                .with_system(
+                    check_target_reached
+                        .system()
+                        .after("apply_velocity")
+                        .before("collision_resolve"),
+                )
+                .with_system(split_mirvs.system().after("targeting"))
+                .with_system(steer_smart_bombs.system().before("apply_velocity"))
+                .with_system(guide_homing_missiles.system().before("apply_velocity"))
+                .with_system(update_target_markers.system()),
         );
     }
 }
@@ -38,6 +100,9 @@ fn spawn_missiles(
     mut commands: Commands,
     asset_handles: Res<AssetHandles>,
     player_status: Res<PlayerStatus>,
+    difficulty: Res<Difficulty>,
+    ballistics: Res<Ballistics>,
+    mut game_rng: ResMut<GameRng>,
     mut events: EventReader<SpawnMissile>,
     mut line_events: EventWriter<SpawnLineTrail>,
 ) {
@@ -45,61 +110,98 @@ fn spawn_missiles(
         let a = Vec2::new(0.0, 1.0);
         let b = e.target - e.position;
         let b = Vec2::new(b.x, b.y);
-        let angle = a.angle_between(b);
 
-        let velocity = match e.team {
-            Team::Player => b.normalize() * player_status.missile_speed,
-            Team::Enemy => b.normalize() * ENEMY_MISSILE_VELOCITY,
+        // Enemy missiles speed up as the difficulty ramps up
+        let speed = match e.team {
+            Team::Player => e.speed,
+            Team::Enemy => e.speed * difficulty.speed,
+        };
+
+        // Guided missiles steer themselves so they always fly straight
+        let homing = e.team == Team::Player && player_status.homing;
+        let ballistic =
+            ballistics.config.enabled && e.missile_type != MissileType::SmartBomb && !homing;
+
+        let (velocity, flight_time) = if ballistic {
+            ballistics.launch(e.team, e.position.truncate(), e.target.truncate(), speed)
+        } else {
+            (b.normalize() * speed, 0.0)
+        };
+        let angle = a.angle_between(velocity);
+
+        let scale = match e.missile_type {
+            MissileType::Standard | MissileType::SmartBomb => Vec3::ONE,
+            MissileType::Mirv => Vec3::splat(MIRV_SCALE),
         };
 
-        let missile_material = match e.team {
-            Team::Player => asset_handles.missile_green.clone(),
-            Team::Enemy => asset_handles.missile_red.clone(),
+        let missile_material = match (e.team, e.missile_type) {
+            (Team::Player, _) => asset_handles.missile_green.clone(),
+            (Team::Enemy, MissileType::SmartBomb) => asset_handles.smart_bomb.clone(),
+            (Team::Enemy, _) => asset_handles.missile_red.clone(),
         };
 
-        // let team = match e.team {
-        //     Team::Player => PlayerTeam,
-        //     Team::Enemy => EnemyTeam,
-        // };
-        // It seems like there isn't a way to conditionally choose which component
-        // to insert without just duplicating the whole commands sequence...
-        let missile_id = if e.team == Team::Player {
+        let missile_id = commands
+            .spawn_bundle(SpriteBundle {
+                material: missile_material,
+                transform: Transform {
+                    translation: e.position,
+                    rotation: Quat::from_rotation_z(angle),
+                    scale,
+                },
+                ..Default::default()
+            })
+            .insert(Velocity(velocity))
+            .insert(Target(e.target))
+            .insert(e.team)
+            .insert(CollisionLayers::missile(e.team))
+            .insert(Missile)
+            .insert(e.missile_type)
+            .insert(CircleCollider(MISSILE_RADIUS))
+            .id();
+
+        match e.missile_type {
+            MissileType::Mirv => {
+                let split_height = game_rng
+                    .stream("spawn_missiles")
+                    .gen_range(MIRV_MIN_SPLIT_HEIGHT..MIRV_MAX_SPLIT_HEIGHT);
+                commands.entity(missile_id).insert(Mirv {
+                    split_height,
+                    speed: e.speed,
+                });
+            }
+            MissileType::SmartBomb => {
+                commands.entity(missile_id).insert(SmartBomb);
+            }
+            MissileType::Standard => {}
+        }
+
+        commands
+            .entity(missile_id)
+            .insert(PreviousPosition(e.position));
+
+        if homing {
+            commands.entity(missile_id).insert(Homing);
+        }
+
+        if ballistic {
             commands
-                .spawn_bundle(SpriteBundle {
-                    material: missile_material,
-                    transform: Transform {
-                        translation: e.position,
-                        rotation: Quat::from_rotation_z(angle),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .insert(Velocity(velocity))
-                .insert(Target(e.target))
-                .insert(PlayerTeam)
-                .insert(Team::Player)
-                .insert(Missile)
-                .insert(CircleCollider(MISSILE_RADIUS))
-                .id()
-        } else {
+                .entity(missile_id)
+                .insert(Ballistic)
+                .insert(Fuse(Timer::from_seconds(flight_time, false)));
+        }
+
+        if let Some(marker) = &e.marker {
             commands
                 .spawn_bundle(SpriteBundle {
-                    material: missile_material,
+                    material: marker.clone(),
                     transform: Transform {
-                        translation: e.position,
-                        rotation: Quat::from_rotation_z(angle),
+                        translation: e.target,
                         ..Default::default()
                     },
                     ..Default::default()
                 })
-                .insert(Velocity(velocity))
-                .insert(Target(e.target))
-                .insert(EnemyTeam)
-                .insert(Team::Enemy)
-                .insert(Missile)
-                .insert(CircleCollider(MISSILE_RADIUS))
-                .id()
-        };
+                .insert(TargetMarker { owner: missile_id });
+        }
 
         // Spawn Line Trail
         line_events.send(SpawnLineTrail {
@@ -112,20 +214,184 @@ fn spawn_missiles(
     }
 }
 
+// Missiles that reach their target are despawned and explode along with the
+// ones that collided with something. Ballistic missiles are left to their Fuse.
 fn check_target_reached(
+    mut events: EventWriter<MissileDetonated>,
+    query: Query<
+        (
+            Entity,
+            &Transform,
+            &Target,
+            &Team,
+            Option<&PreviousPosition>,
+        ),
+        Without<Fuse>,
+    >,
+) {
+    for (entity, transform, target, team, previous) in query.iter() {
+        // Fast missiles can move past their target in a single frame
+        let reached = segment_hits_circle(
+            start_position(transform, previous),
+            transform.translation.truncate(),
+            target.0.truncate(),
+            TARGET_REACHED_RADIUS,
+        );
+
+        if reached {
+            events.send(MissileDetonated {
+                missile: entity,
+                team: *team,
+                position: target.0,
+            });
+        }
+    }
+}
+
+// Replace MIRVs that have reached their split altitude with several standard
+// missiles. Each warhead is aimed at a different target and their line trails
+// start from the split point.
+fn split_mirvs(
     mut commands: Commands,
-    mut events: EventWriter<SpawnExplosion>,
-    player_status: Res<PlayerStatus>,
-    query: Query<(Entity, &Transform, &Target, &Team)>,
+    targeting: Res<Targeting>,
+    mut game_rng: ResMut<GameRng>,
+    windows: Res<Windows>,
+    ground_level: Res<GroundLevel>,
+    query: Query<(Entity, &Transform, &Mirv)>,
+    mut events: EventWriter<SpawnMissile>,
 ) {
-    for (entity, transform, target, team) in query.iter() {
-        if transform.translation.distance_squared(target.0) < 10.0 {
-            commands.entity(entity).despawn();
-            events.send(SpawnExplosion {
+    let rng = game_rng.stream("split_mirvs");
+    let window_height = windows.get_primary().unwrap().height();
+
+    for (entity, transform, mirv) in query.iter() {
+        let split_altitude =
+            ground_level.height_at(transform.translation.x) + mirv.split_height * window_height;
+        if transform.translation.y > split_altitude {
+            continue;
+        }
+
+        commands.entity(entity).despawn();
+
+        for target in targeting.pick_many(MIRV_WARHEADS, rng) {
+            events.send(SpawnMissile {
                 position: transform.translation,
-                team: *team,
-                size: player_status.explosion_size,
+                target,
+                team: Team::Enemy,
+                missile_type: MissileType::Standard,
+                speed: mirv.speed,
+                marker: None,
+            });
+        }
+    }
+}
+
+// Turn smart bombs away from any player explosion they are about to fly into.
+// The closer the bomb is to the edge of an explosion the harder it turns.
+fn steer_smart_bombs(
+    time: Res<Time>,
+    explosions: Query<(&Transform, &CircleCollider, &CollisionLayers), With<Explosion>>,
+    mut smart_bombs: Query<
+        (&mut Transform, &mut Velocity, &Target, &CollisionLayers),
+        (With<SmartBomb>, Without<Explosion>),
+    >,
+) {
+    for (mut transform, mut velocity, target, layers) in smart_bombs.iter_mut() {
+        let position = transform.translation.truncate();
+        let speed = velocity.0.length();
+
+        let mut desired = (target.0.truncate() - position).normalize_or_zero();
+        for (explosion_transform, collider, explosion_layers) in explosions.iter() {
+            // Only dodge explosions that can destroy the bomb
+            if !explosion_layers.hits(layers) {
+                continue;
+            }
+
+            let away = position - explosion_transform.translation.truncate();
+            let gap = away.length() - collider.0;
+
+            if gap < SMART_BOMB_AVOID_DISTANCE {
+                let strength = 1.0 - gap.max(0.0) / SMART_BOMB_AVOID_DISTANCE;
+                desired += away.normalize_or_zero() * strength * 2.0;
+            }
+        }
+
+        if desired == Vec2::ZERO {
+            continue;
+        }
+
+        let max_angle = SMART_BOMB_TURN_RATE * time.delta_seconds();
+        velocity.0 = rotate_towards(velocity.0, desired, max_angle).normalize() * speed;
+        transform.rotation = Quat::from_rotation_z(Vec2::new(0.0, 1.0).angle_between(velocity.0));
+    }
+}
+
+// Turn homing missiles towards the closest missile they can hit inside their
+// cone. Their target follows the locked missile so if the lock is lost they
+// still explode where it was last seen.
+fn guide_homing_missiles(
+    time: Res<Time>,
+    other_missiles: Query<(&Transform, &CollisionLayers), (With<Missile>, Without<Homing>)>,
+    mut homing_missiles: Query<
+        (&mut Transform, &mut Velocity, &mut Target, &CollisionLayers),
+        With<Homing>,
+    >,
+) {
+    for (mut transform, mut velocity, mut target, layers) in homing_missiles.iter_mut() {
+        let position = transform.translation.truncate();
+
+        let lock = other_missiles
+            .iter()
+            .filter(|(_, other_layers)| layers.hits(other_layers))
+            .map(|(t, _)| t.translation.truncate())
+            .filter(|enemy| {
+                let to_enemy = *enemy - position;
+                to_enemy.length_squared() < HOMING_RANGE.powi(2)
+                    && velocity.0.angle_between(to_enemy).abs() < HOMING_CONE
+            })
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
             });
+
+        if let Some(enemy) = lock {
+            target.0 = enemy.extend(target.0.z);
+        }
+
+        let desired = target.0.truncate() - position;
+        if desired == Vec2::ZERO {
+            continue;
+        }
+
+        let speed = velocity.0.length();
+        let max_angle = HOMING_TURN_RATE * time.delta_seconds();
+        velocity.0 = rotate_towards(velocity.0, desired, max_angle).normalize() * speed;
+        transform.rotation = Quat::from_rotation_z(Vec2::new(0.0, 1.0).angle_between(velocity.0));
+    }
+}
+
+// Keep markers on their missile's target, which can move for homing missiles,
+// and remove them once the missile has exploded or been destroyed
+fn update_target_markers(
+    mut commands: Commands,
+    missiles: Query<&Target>,
+    mut markers: Query<(Entity, &TargetMarker, &mut Transform)>,
+) {
+    for (entity, marker, mut transform) in markers.iter_mut() {
+        match missiles.get(marker.owner) {
+            Ok(target) => transform.translation = target.0,
+            Err(_) => commands.entity(entity).despawn(),
         }
     }
 }
+
+// Rotate `current` towards `desired` by no more than `max_angle` radians
+pub fn rotate_towards(current: Vec2, desired: Vec2, max_angle: f32) -> Vec2 {
+    let angle = current.angle_between(desired);
+    let angle = angle.clamp(-max_angle, max_angle);
+    let (sin, cos) = angle.sin_cos();
+
+    Vec2::new(
+        current.x * cos - current.y * sin,
+        current.x * sin + current.y * cos,
+    )
+}
@@ -1,13 +1,14 @@
 use bevy::prelude::*;
 
 use crate::{
-    consts::{EXPLOSION_SIZE_TIME, MISSILE_SPEED_TIME, PLAYER_MISSILE_VELOCITY},
+    consts::{EXPLOSION_SIZE_TIME, HOMING_TIME, MISSILE_SPEED_TIME, PLAYER_MISSILE_VELOCITY},
     GameState,
 };
 
 // Events
 pub struct SetPlayerExplosionSize(pub f32);
 pub struct SetPlayerMissileSpeed(pub f32);
+pub struct SetPlayerHoming(pub bool);
 
 // Resource to store player missile velocity, explosion size, etc...
 // TODO - Add ui indicators to show powerup status?
@@ -16,6 +17,8 @@ pub struct PlayerStatus {
     pub explosion_timer: Timer,
     pub missile_speed: f32,
     pub missile_timer: Timer,
+    pub homing: bool,
+    pub homing_timer: Timer,
 }
 
 impl PlayerStatus {
@@ -24,6 +27,8 @@ impl PlayerStatus {
         self.explosion_timer = Timer::from_seconds(EXPLOSION_SIZE_TIME, false);
         self.missile_speed = PLAYER_MISSILE_VELOCITY;
         self.missile_timer.reset();
+        self.homing = false;
+        self.homing_timer.reset();
     }
 }
 
@@ -34,6 +39,8 @@ impl Default for PlayerStatus {
             explosion_timer: Timer::from_seconds(EXPLOSION_SIZE_TIME, false),
             missile_speed: PLAYER_MISSILE_VELOCITY,
             missile_timer: Timer::from_seconds(MISSILE_SPEED_TIME, false),
+            homing: false,
+            homing_timer: Timer::from_seconds(HOMING_TIME, false),
         }
     }
 }
@@ -43,6 +50,7 @@ impl Plugin for PlayerStatusPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_event::<SetPlayerExplosionSize>()
             .add_event::<SetPlayerMissileSpeed>()
+            .add_event::<SetPlayerHoming>()
             .init_resource::<PlayerStatus>()
             .add_system_set(
                 SystemSet::on_update(GameState::Game)
@@ -62,12 +70,16 @@ fn run_timers(time: Res<Time>, mut player_status: ResMut<PlayerStatus>) {
     if player_status.missile_timer.tick(time.delta()).finished() {
         player_status.missile_speed = PLAYER_MISSILE_VELOCITY;
     }
+    if player_status.homing_timer.tick(time.delta()).finished() {
+        player_status.homing = false;
+    }
 }
 
 fn handle_events(
     mut player_status: ResMut<PlayerStatus>,
     mut explosion_size_events: EventReader<SetPlayerExplosionSize>,
     mut missile_speed_events: EventReader<SetPlayerMissileSpeed>,
+    mut homing_events: EventReader<SetPlayerHoming>,
 ) {
     for e in explosion_size_events.iter() {
         player_status.explosion_size = e.0;
@@ -77,6 +89,10 @@ fn handle_events(
         player_status.missile_speed = e.0;
         player_status.missile_timer.reset();
     }
+    for e in homing_events.iter() {
+        player_status.homing = e.0;
+        player_status.homing_timer.reset();
+    }
 }
 
 fn reset_player_status(mut player_status: ResMut<PlayerStatus>) {
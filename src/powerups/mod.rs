@@ -15,6 +15,7 @@ pub enum PowerupType {
     Score,
     ExplosionSize,
     MissileSpeed,
+    Homing,
 }
 
 pub struct PowerupsPlugin;
@@ -2,7 +2,13 @@ use bevy::prelude::*;
 use rand::prelude::*;
 
 use super::PowerupType;
-use crate::{collision::CircleCollider, consts::POWERUP_RADIUS, AssetHandles, Velocity};
+use crate::{
+    collision::{CircleCollider, PreviousPosition},
+    consts::POWERUP_RADIUS,
+    rng::GameRng,
+    team::CollisionLayers,
+    AssetHandles, Velocity,
+};
 
 pub struct PowerupSpawner {
     pub timer: Timer,
@@ -18,6 +24,7 @@ pub struct SpawnPowerup {
 pub fn run_powerup_spawner(
     time: Res<Time>,
     windows: Res<Windows>,
+    mut game_rng: ResMut<GameRng>,
     mut spawner: ResMut<PowerupSpawner>,
     mut events: EventWriter<SpawnPowerup>,
 ) {
@@ -27,7 +34,7 @@ pub fn run_powerup_spawner(
             windows.get_primary().unwrap().height() / 2.0,
         );
 
-        let mut rng = thread_rng();
+        let rng = game_rng.stream("powerup_spawner");
         let y = rng.gen_range(0.0..half_height);
         let x = match rng.gen_bool(0.5) {
             true => -half_width - POWERUP_RADIUS,
@@ -41,9 +48,10 @@ pub fn run_powerup_spawner(
         };
 
         let powerup_type = match rng.gen_range(0.0..1.0) {
-            num if num < 0.33 => PowerupType::Score,
-            num if num > 0.67 => PowerupType::ExplosionSize,
-            _ => PowerupType::MissileSpeed,
+            num if num < 0.25 => PowerupType::Score,
+            num if num < 0.5 => PowerupType::ExplosionSize,
+            num if num < 0.75 => PowerupType::MissileSpeed,
+            _ => PowerupType::Homing,
         };
 
         events.send(SpawnPowerup {
@@ -66,6 +74,7 @@ pub fn spawn_powerups(
                     PowerupType::Score => asset_handles.score_powerup.clone(),
                     PowerupType::ExplosionSize => asset_handles.explosion_size_powerup.clone(),
                     PowerupType::MissileSpeed => asset_handles.missile_speed_powerup.clone(),
+                    PowerupType::Homing => asset_handles.homing_powerup.clone(),
                 },
                 transform: Transform {
                     translation: e.position,
@@ -75,6 +84,11 @@ pub fn spawn_powerups(
             })
             .insert(e.powerup_type)
             .insert(Velocity(e.velocity))
-            .insert(CircleCollider(POWERUP_RADIUS));
+            .insert(CircleCollider(POWERUP_RADIUS))
+            .insert(CollisionLayers::new(
+                CollisionLayers::POWERUP,
+                CollisionLayers::NONE,
+            ))
+            .insert(PreviousPosition(e.position));
     }
 }
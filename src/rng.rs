@@ -0,0 +1,90 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::{prelude::*, utils::HashMap};
+use rand::{prelude::*, rngs::StdRng};
+
+use crate::state::GameState;
+
+// Resource that all gameplay randomness is drawn from so a game can be
+// replayed by starting it with the same seed.
+//
+// Systems run in parallel so the order they draw numbers in isn't fixed
+// between runs. To keep a seed reproducible each system draws from its own
+// stream, named after the system and seeded from the game seed.
+pub struct GameRng {
+    seed: u64,
+    streams: HashMap<&'static str, StdRng>,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: HashMap::default(),
+        }
+    }
+
+    // Read the seed from the command line, `--seed <number>` uses the given
+    // seed and `--daily` uses the same seed for everyone playing on a given
+    // day. Otherwise a random seed is used.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+
+        let seed = if let Some(i) = args.iter().position(|arg| arg == "--seed") {
+            match args.get(i + 1).map(|seed| seed.parse::<u64>()) {
+                Some(Ok(seed)) => seed,
+                _ => {
+                    println!("--seed must be followed by a number");
+                    std::process::exit(1);
+                }
+            }
+        } else if args.iter().any(|arg| arg == "--daily") {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+            now.as_secs() / (60 * 60 * 24)
+        } else {
+            thread_rng().gen()
+        };
+
+        Self::new(seed)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn stream(&mut self, name: &'static str) -> &mut StdRng {
+        let seed = self.seed;
+        self.streams
+            .entry(name)
+            .or_insert_with(|| StdRng::seed_from_u64(seed ^ hash_name(name)))
+    }
+
+    // Restart every stream from the beginning so the next game plays out
+    // the same way
+    fn reset(&mut self) {
+        self.streams.clear();
+    }
+}
+
+// FNV-1a, used instead of the std hasher so stream seeds never change
+// between builds
+fn hash_name(name: &str) -> u64 {
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+pub struct GameRngPlugin;
+impl Plugin for GameRngPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        let rng = GameRng::from_args();
+        println!("Seed: {}", rng.seed());
+
+        app.insert_resource(rng)
+            .add_system_set(SystemSet::on_exit(GameState::Game).with_system(reset_rng.system()));
+    }
+}
+
+fn reset_rng(mut rng: ResMut<GameRng>) {
+    rng.reset();
+}
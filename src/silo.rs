@@ -1,44 +1,81 @@
 use bevy::prelude::*;
 
-use crate::{state::GameState, AssetHandles, SILO_MAX_MISSILES};
+use crate::{
+    consts::{SMART_FIRE_MISSILE_COST, SMART_FIRE_THREAT_COST},
+    debris::DebrisType,
+    state::GameState,
+    team::CollisionLayers,
+    AssetHandles, SILO_MAX_MISSILES,
+};
 
 pub struct Silo {
-    pub location: SiloLocation,
+    // Silos are numbered from the left starting at 0
+    pub index: usize,
     pub missiles: u8,
+    // Material for the target markers of missiles fired from this silo
+    pub marker: Handle<ColorMaterial>,
 }
 
-#[derive(PartialEq, Debug, Clone, Copy)]
-pub enum SiloLocation {
-    Left,
-    Middle,
-    Right,
+// Silo destroyed by the enemy. It isn't despawned so it keeps its number and
+// still counts towards the silo totals, but it has no missiles, no UI and
+// nothing collides with it until it is rebuilt.
+pub struct Wrecked;
+
+// Name of the action that fires silo `index`, counted from 1 for the player
+pub fn fire_action(index: usize) -> String {
+    format!("Fire Silo {}", index + 1)
+}
+
+// Action that fires whichever silo `fire_cost` picks
+pub const SMART_FIRE_ACTION: &str = "Fire";
+
+// How bad a choice this silo is for smart fire, lower is better. Mostly the
+// `flight_time` of its missile to the target, but silos that are running low
+// are saved and silos with `threats` incoming fire first since their
+// missiles are lost if they are destroyed.
+pub fn fire_cost(silo: &Silo, flight_time: f32, threats: usize) -> f32 {
+    let missing = (SILO_MAX_MISSILES - silo.missiles.min(SILO_MAX_MISSILES)) as f32;
+
+    flight_time + missing * SMART_FIRE_MISSILE_COST - threats as f32 * SMART_FIRE_THREAT_COST
 }
 
-pub struct SiloReloadUi;
+pub struct SiloReloadUi {
+    pub silo: usize,
+}
 
-pub struct SiloMissileCountUi;
+pub struct SiloMissileCountUi {
+    pub silo: usize,
+}
 
-// Event
+// Events
 pub struct SiloMissileCountUpdate {
-    pub location: SiloLocation,
+    pub silo: usize,
     pub count: u8,
 }
+// Put a wrecked silo back into action, it starts empty and reloads as normal
+pub struct RebuildSilo {
+    pub silo: usize,
+}
 
 pub struct SiloPlugin;
 impl Plugin for SiloPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.add_event::<SiloMissileCountUpdate>().add_system_set(
-            SystemSet::on_update(GameState::Game)
-                .with_system(check_timers.system())
-                .with_system(update_reload_ui.system())
-                .with_system(update_missile_count_ui.system()),
-        );
+        app.add_event::<SiloMissileCountUpdate>()
+            .add_event::<RebuildSilo>()
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(check_timers.system())
+                    .with_system(update_reload_ui.system())
+                    .with_system(update_missile_count_ui.system())
+                    .with_system(hide_wrecked_ui.system())
+                    .with_system(rebuild_silos.system()),
+            );
     }
 }
 
 fn check_timers(
     time: Res<Time>,
-    mut query: Query<(&mut Silo, &mut Timer)>,
+    mut query: Query<(&mut Silo, &mut Timer), Without<Wrecked>>,
     mut events: EventWriter<SiloMissileCountUpdate>,
 ) {
     for (mut silo, mut timer) in query.iter_mut() {
@@ -48,7 +85,7 @@ fn check_timers(
                 timer.reset();
             }
             events.send(SiloMissileCountUpdate {
-                location: silo.location,
+                silo: silo.index,
                 count: silo.missiles,
             });
         }
@@ -57,17 +94,12 @@ fn check_timers(
 
 fn update_reload_ui(
     asset_handles: Res<AssetHandles>,
-    query: Query<(&Silo, &Timer, &Transform), Without<SiloReloadUi>>,
-    mut ui_query: Query<(
-        &SiloReloadUi,
-        &SiloLocation,
-        &mut Transform,
-        &mut Handle<ColorMaterial>,
-    )>,
+    query: Query<(&Silo, &Timer, &Transform), (Without<SiloReloadUi>, Without<Wrecked>)>,
+    mut ui_query: Query<(&SiloReloadUi, &mut Transform, &mut Handle<ColorMaterial>)>,
 ) {
     for (silo, timer, silo_transform) in query.iter() {
-        for (_, location, mut ui_transform, mut mat) in ui_query.iter_mut() {
-            if silo.location == *location {
+        for (ui, mut ui_transform, mut mat) in ui_query.iter_mut() {
+            if silo.index == ui.silo {
                 ui_transform.translation.x =
                     silo_transform.translation.x - 25.0 * timer.percent_left();
                 ui_transform.scale.x = timer.percent();
@@ -85,13 +117,13 @@ fn update_reload_ui(
 }
 
 fn update_missile_count_ui(
-    query: Query<(&SiloMissileCountUi, &SiloLocation, &Children)>,
+    query: Query<(&SiloMissileCountUi, &Children)>,
     mut visible_query: Query<&mut Visible>,
     mut events: EventReader<SiloMissileCountUpdate>,
 ) {
     for e in events.iter() {
-        for (_, location, children) in query.iter() {
-            if *location == e.location {
+        for (ui, children) in query.iter() {
+            if ui.silo == e.silo {
                 match e.count {
                     0 => {
                         visible_query.get_mut(children[0]).unwrap().is_visible = false;
@@ -119,3 +151,64 @@ fn update_missile_count_ui(
         }
     }
 }
+
+// The missile count is emptied when the silo is wrecked, which just leaves the
+// reload bar to hide
+fn hide_wrecked_ui(
+    query: Query<&Silo, Added<Wrecked>>,
+    mut ui_query: Query<(&SiloReloadUi, &mut Visible)>,
+) {
+    for silo in query.iter() {
+        for (ui, mut visible) in ui_query.iter_mut() {
+            if ui.silo == silo.index {
+                visible.is_visible = false;
+            }
+        }
+    }
+}
+
+fn rebuild_silos(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &Silo,
+            &Transform,
+            &mut Timer,
+            &mut CollisionLayers,
+            &mut Visible,
+        ),
+        (With<Wrecked>, Without<SiloReloadUi>),
+    >,
+    mut ui_query: Query<(&SiloReloadUi, &mut Visible), Without<Silo>>,
+    debris: Query<(Entity, &DebrisType, &Transform)>,
+    mut events: EventReader<RebuildSilo>,
+) {
+    for e in events.iter() {
+        for (entity, silo, transform, mut timer, mut layers, mut visible) in query.iter_mut() {
+            if silo.index != e.silo {
+                continue;
+            }
+
+            commands.entity(entity).remove::<Wrecked>();
+            *layers = CollisionLayers::new(CollisionLayers::STRUCTURE, CollisionLayers::NONE);
+            visible.is_visible = true;
+            timer.reset();
+
+            for (ui, mut ui_visible) in ui_query.iter_mut() {
+                if ui.silo == silo.index {
+                    ui_visible.is_visible = true;
+                }
+            }
+
+            // Clear away the wreckage left where the silo stood
+            for (debris_entity, debris_type, debris_transform) in debris.iter() {
+                if let DebrisType::Silo = debris_type {
+                    if (debris_transform.translation.x - transform.translation.x).abs() < 1.0 {
+                        commands.entity(debris_entity).despawn();
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use bevy::{
+    diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+};
+use rand::prelude::*;
+
+use crate::{
+    collision::{SpatialGrid, COLLISION_CHECKS},
+    consts::{STRESS_COMPARE_TIME, STRESS_EXPLOSIONS, STRESS_EXPLOSION_TIME},
+    debris::DebrisType,
+    explosion::SpawnExplosion,
+    rng::GameRng,
+    silo::{RebuildSilo, Silo, Wrecked},
+    state::GameState,
+    team::Team,
+    SpawnBuilding,
+};
+
+// Benchmark scenario for the collision systems, run the game with `--stress`.
+// The waves come from stress_waves.ron instead, player explosions keep going
+// off across the sky and destroyed cities and silos are rebuilt straight away
+// so the game doesn't end. Frame times and collision checks are logged every
+// second, and the spatial grid is turned on and off to show what it saves.
+pub fn enabled() -> bool {
+    std::env::args().any(|arg| arg == "--stress")
+}
+
+struct StressTimer(Timer);
+
+// Average frame time and collision checks over one stretch of the comparison
+#[derive(Default)]
+struct Sample {
+    frames: u32,
+    frame_time: f64,
+    checks: f64,
+}
+
+impl Sample {
+    fn add(&mut self, frame_time: f64, checks: f64) {
+        self.frames += 1;
+        self.frame_time += frame_time;
+        self.checks += checks;
+    }
+
+    // Average milliseconds per frame and checks per frame
+    fn averages(&self) -> (f64, f64) {
+        let frames = self.frames.max(1) as f64;
+        (self.frame_time * 1000.0 / frames, self.checks / frames)
+    }
+}
+
+// The spatial grid is switched on and off every `STRESS_COMPARE_TIME` seconds
+// and the last stretch of each is logged side by side
+struct GridComparison {
+    timer: Timer,
+    grid: Sample,
+    no_grid: Sample,
+}
+
+pub struct StressPlugin;
+impl Plugin for StressPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        if !enabled() {
+            return;
+        }
+
+        app.insert_resource(StressTimer(Timer::from_seconds(
+            STRESS_EXPLOSION_TIME,
+            true,
+        )))
+        .insert_resource(GridComparison {
+            timer: Timer::from_seconds(STRESS_COMPARE_TIME, true),
+            grid: Sample::default(),
+            no_grid: Sample::default(),
+        })
+        .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_plugin(LogDiagnosticsPlugin::default())
+        .add_system_set(
+            SystemSet::on_update(GameState::Game)
+                .with_system(spawn_explosions.system())
+                .with_system(rebuild_structures.system())
+                .with_system(compare_grid.system()),
+        );
+    }
+}
+
+// Explosions go off in the top half of the screen so there's plenty for the
+// enemy missiles to fly through
+fn spawn_explosions(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    mut timer: ResMut<StressTimer>,
+    mut game_rng: ResMut<GameRng>,
+    mut events: EventWriter<SpawnExplosion>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let window = windows.get_primary().unwrap();
+    let (half_width, half_height) = (window.width() / 2.0, window.height() / 2.0);
+    let rng = game_rng.stream("stress");
+
+    events.send_batch((0..STRESS_EXPLOSIONS).map(|_| SpawnExplosion {
+        position: Vec3::new(
+            rng.gen_range(-half_width..half_width),
+            rng.gen_range(0.0..half_height),
+            0.0,
+        ),
+        team: Team::Player,
+        size: 1.0,
+        chain: 0,
+    }));
+}
+
+// Silos are only sent to be rebuilt once, the frame they are wrecked
+fn rebuild_structures(
+    mut commands: Commands,
+    debris: Query<(Entity, &DebrisType, &Transform)>,
+    wrecked_silos: Query<&Silo, Added<Wrecked>>,
+    mut events: EventWriter<SpawnBuilding>,
+    mut silo_events: EventWriter<RebuildSilo>,
+) {
+    for (entity, debris_type, transform) in debris.iter() {
+        if let DebrisType::Building = debris_type {
+            commands.entity(entity).despawn();
+            events.send(SpawnBuilding {
+                x_position: transform.translation.x,
+            });
+        }
+    }
+
+    for silo in wrecked_silos.iter() {
+        silo_events.send(RebuildSilo { silo: silo.index });
+    }
+}
+
+fn compare_grid(
+    time: Res<Time>,
+    diagnostics: Res<Diagnostics>,
+    mut grid: ResMut<SpatialGrid>,
+    mut comparison: ResMut<GridComparison>,
+) {
+    let checks = diagnostics
+        .get(COLLISION_CHECKS)
+        .and_then(|diagnostic| diagnostic.value())
+        .unwrap_or(0.0);
+    let frame_time = time.delta_seconds_f64();
+
+    if grid.config.enabled {
+        comparison.grid.add(frame_time, checks);
+    } else {
+        comparison.no_grid.add(frame_time, checks);
+    }
+
+    if !comparison.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    grid.config.enabled = !grid.config.enabled;
+
+    // Report once both have had a turn, which is every other switch
+    if comparison.grid.frames > 0 && comparison.no_grid.frames > 0 {
+        let (grid_ms, grid_checks) = comparison.grid.averages();
+        let (no_grid_ms, no_grid_checks) = comparison.no_grid.averages();
+        info!(
+            "Spatial grid on: {:.2}ms {:.0} checks | off: {:.2}ms {:.0} checks | {:.1}x faster",
+            grid_ms,
+            grid_checks,
+            no_grid_ms,
+            no_grid_checks,
+            no_grid_ms / grid_ms.max(f64::EPSILON),
+        );
+        comparison.grid = Sample::default();
+        comparison.no_grid = Sample::default();
+    }
+}
@@ -1,8 +1,8 @@
 /*-----------------------------------------------------------------------------
-    The `PlayerTeam` and `EnemyTeam` components below exist to make collision
-    querying easier. However some entities need to be able to pass info to
-    spawn events (e.g. Missile spawning an explosion) and require both a
-    `Team` and `PlayerTeam`/`EnemyTeam` component.
+    `Team` says who an entity belongs to and is passed along in spawn events
+    (e.g. Missile spawning an explosion). What actually collides with what is
+    decided by `CollisionLayers`, so adding a new kind of collision only means
+    changing a mask rather than adding more queries.
 -----------------------------------------------------------------------------*/
 
 #[derive(Clone, Copy, PartialEq)]
@@ -11,6 +11,50 @@ pub enum Team {
     Enemy,
 }
 
-pub struct PlayerTeam;
+// `layer` is the kind of thing an entity is and `mask` is every kind of thing
+// it can hit. Only the side doing the hitting checks its mask, so the player's
+// structures don't need to know what destroys them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionLayers {
+    pub layer: u32,
+    pub mask: u32,
+}
+
+impl CollisionLayers {
+    pub const NONE: u32 = 0;
+    pub const PLAYER_MISSILE: u32 = 1 << 0;
+    pub const PLAYER_EXPLOSION: u32 = 1 << 1;
+    pub const ENEMY_MISSILE: u32 = 1 << 2;
+    pub const ENEMY_EXPLOSION: u32 = 1 << 3;
+    // Fliers and the boss
+    pub const ENEMY_CRAFT: u32 = 1 << 4;
+    // Buildings and silos
+    pub const STRUCTURE: u32 = 1 << 5;
+    pub const POWERUP: u32 = 1 << 6;
+
+    pub const fn new(layer: u32, mask: u32) -> Self {
+        Self { layer, mask }
+    }
 
-pub struct EnemyTeam;
+    pub fn missile(team: Team) -> Self {
+        match team {
+            Team::Player => Self::new(Self::PLAYER_MISSILE, Self::ENEMY_MISSILE | Self::POWERUP),
+            Team::Enemy => Self::new(Self::ENEMY_MISSILE, Self::STRUCTURE),
+        }
+    }
+
+    pub fn explosion(team: Team) -> Self {
+        match team {
+            Team::Player => Self::new(
+                Self::PLAYER_EXPLOSION,
+                Self::ENEMY_MISSILE | Self::ENEMY_CRAFT | Self::POWERUP,
+            ),
+            Team::Enemy => Self::new(Self::ENEMY_EXPLOSION, Self::STRUCTURE),
+        }
+    }
+
+    // Check if this entity's mask covers the other entity's layer
+    pub fn hits(&self, other: &CollisionLayers) -> bool {
+        self.mask & other.layer != 0
+    }
+}
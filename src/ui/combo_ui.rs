@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+use crate::{state::GameState, AssetHandles};
+
+struct ComboUi;
+
+struct ComboUiText;
+
+// Update chain length Event, 0 once the chain has ended
+pub struct UpdateComboUi(pub u32);
+
+pub struct ComboUiPlugin;
+impl Plugin for ComboUiPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_event::<UpdateComboUi>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game).with_system(setup_combo_ui.system()),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game).with_system(update_combo_ui.system()),
+            )
+            .add_system_set(SystemSet::on_exit(GameState::Game).with_system(despawn.system()));
+    }
+}
+
+// Chain length is shown in the top right corner, opposite the score
+fn setup_combo_ui(mut commands: Commands, asset_handles: Res<AssetHandles>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                display: Display::Flex,
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::FlexEnd,
+                ..Default::default()
+            },
+            material: asset_handles.none.clone(),
+            ..Default::default()
+        })
+        .insert(ComboUi)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text {
+                        sections: vec![TextSection {
+                            value: "".to_string(),
+                            style: TextStyle {
+                                font: asset_handles.font.clone(),
+                                font_size: 28.0,
+                                color: Color::rgb(0.49, 0.87, 0.49),
+                            },
+                        }],
+                        ..Default::default()
+                    },
+                    style: Style {
+                        margin: Rect::all(Val::Px(15.0)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(ComboUiText);
+        });
+}
+
+fn update_combo_ui(
+    mut query: Query<&mut Text, With<ComboUiText>>,
+    mut events: EventReader<UpdateComboUi>,
+) {
+    for e in events.iter() {
+        for mut text in query.iter_mut() {
+            // A single link isn't a chain yet
+            text.sections[0].value = if e.0 > 1 {
+                format!("CHAIN x{}", e.0)
+            } else {
+                "".to_string()
+            };
+        }
+    }
+}
+
+fn despawn(mut commands: Commands, query: Query<(Entity, &ComboUi)>) {
+    for (entity, _) in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
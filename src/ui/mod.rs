@@ -2,18 +2,22 @@ use bevy::prelude::*;
 
 use crate::AssetHandles;
 
+mod combo_ui;
 mod game_over;
 mod main_menu;
 mod options_menu;
 mod pause_menu;
 mod score_ui;
+mod wave_ui;
 
 pub use self::{
+    combo_ui::{ComboUiPlugin, UpdateComboUi},
     game_over::GameOverPlugin,
     main_menu::MainMenuPlugin,
     options_menu::OptionsMenuPlugin,
     pause_menu::PauseMenuPlugin,
     score_ui::{ScoreUiPlugin, UpdateScoreUi},
+    wave_ui::WaveUiPlugin,
 };
 
 enum ButtonType {
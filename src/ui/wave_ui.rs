@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use crate::{
+    enemy::{WaveCleared, WaveStarted},
+    silo::{Silo, Wrecked},
+    state::GameState,
+    AssetHandles,
+};
+
+// How long the wave banner stays on screen
+const BANNER_TIME: f32 = 2.0;
+
+struct WaveUi;
+
+struct WaveUiText {
+    timer: Timer,
+}
+
+pub struct WaveUiPlugin;
+impl Plugin for WaveUiPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_system_set(
+            SystemSet::on_enter(GameState::Game).with_system(setup_wave_ui.system()),
+        )
+        .add_system_set(SystemSet::on_update(GameState::Game).with_system(update_wave_ui.system()))
+        .add_system_set(SystemSet::on_exit(GameState::Game).with_system(despawn.system()));
+    }
+}
+
+fn setup_wave_ui(mut commands: Commands, asset_handles: Res<AssetHandles>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                display: Display::Flex,
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: asset_handles.none.clone(),
+            ..Default::default()
+        })
+        .insert(WaveUi)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text {
+                        sections: vec![TextSection {
+                            value: "".to_string(),
+                            style: TextStyle {
+                                font: asset_handles.font.clone(),
+                                font_size: 40.0,
+                                color: Color::rgb(0.9, 0.9, 0.9),
+                            },
+                        }],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(WaveUiText {
+                    timer: Timer::from_seconds(BANNER_TIME, false),
+                });
+        });
+}
+
+fn update_wave_ui(
+    time: Res<Time>,
+    mut query: Query<(&mut WaveUiText, &mut Text)>,
+    silos: Query<Option<&Wrecked>, With<Silo>>,
+    mut wave_started_events: EventReader<WaveStarted>,
+    mut wave_cleared_events: EventReader<WaveCleared>,
+) {
+    for (mut wave_ui, mut text) in query.iter_mut() {
+        for e in wave_started_events.iter() {
+            text.sections[0].value = format!("WAVE {}", e.0);
+            wave_ui.timer.reset();
+        }
+        for e in wave_cleared_events.iter() {
+            let standing = silos.iter().filter(|wrecked| wrecked.is_none()).count();
+            text.sections[0].value = format!(
+                "WAVE {} CLEARED\n{}/{} SILOS STANDING",
+                e.0,
+                standing,
+                silos.iter().count()
+            );
+            wave_ui.timer.reset();
+        }
+
+        if wave_ui.timer.tick(time.delta()).just_finished() {
+            text.sections[0].value = "".to_string();
+        }
+    }
+}
+
+fn despawn(mut commands: Commands, query: Query<(Entity, &WaveUi)>) {
+    for (entity, _) in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}